@@ -12,17 +12,23 @@ use crate::data::enums::*;
 use crate::data::id::*;
 use crate::data::link::{Link, Linkable};
 use crate::data::preview::*;
+use crate::data::score::Score;
 use crate::general::cache::Cache;
 use crate::general::file;
 use crate::general::timer::Timer;
 use crate::general::worker::Worker;
 use crate::input::raw::RawData;
+use crate::input::source::ImplicationRelation;
 use crate::output::api;
-use crate::output::dot::{DotEdge, DotGraph};
+use crate::output::diagram::{hasse_diagram, knowledge_edge_style, parameter_edge_style, parameter_edges};
+use crate::output::dot::DotEdgeAttribute;
 use crate::output::markdown::{GeneratedPage, Markdown};
 use crate::output::pages::TargetPage;
 use crate::output::pages::{self, Substitute, add_content};
 use crate::output::table::generate_relation_table;
+use crate::work::contradiction::contradiction_report;
+use crate::work::hide::redundancy_report;
+use crate::work::incremental::ProcessedRelations;
 use crate::work::processing::process_raw_data;
 
 use super::Args;
@@ -86,7 +92,13 @@ impl Computation {
                 None
             }
         };
-        let data_cache: Cache<Data> = Cache::new(&self.paths.tmp_dir.join("data.json"));
+        self.time.print("retrieving data collection");
+        let mut rawdata = collection_fn();
+        let bibliography_content = file::read_file_content(&self.paths.bibliography_file).unwrap_or_default();
+        let data_cache: Cache<Data> = Cache::new(&self.paths.tmp_dir.join("data.json"))
+            .depends_on(&rawdata)
+            .depends_on(&bibliography_content)
+            .depends_on(&env!("CARGO_PKG_VERSION"));
         if !self.args.contains(&Args::Preprocess)
             && let Some(mut res) = data_cache.load()
         {
@@ -94,10 +106,15 @@ impl Computation {
             self.some_data = Some(res);
             return;
         }
-        self.time.print("retrieving data collection");
-        let mut rawdata = collection_fn();
         self.time.print("processing data");
-        let res = process_raw_data(rawdata, &self.bibliography);
+        let relations_cache: Cache<ProcessedRelations> =
+            Cache::new(&self.paths.tmp_dir.join("relations.json")).depends_on(&env!("CARGO_PKG_VERSION"));
+        let (res, relations) = process_raw_data(rawdata, &self.bibliography, relations_cache.load());
+        if res.contradictions.is_empty()
+            && let Err(err) = relations_cache.save(&relations)
+        {
+            info!("{:?}", err);
+        }
         match data_cache.save(&res) {
             Ok(()) => {}
             Err(err) => info!("{:?}", err),
@@ -105,6 +122,36 @@ impl Computation {
         self.some_data = Some(res);
     }
 
+    /// Writes the contradiction report and stops the run if the data is inconsistent.
+    pub(crate) fn check_contradictions(&self) {
+        let data = self.get_data();
+        let report_file = self.paths.tmp_dir.join("contradictions.md");
+        if let Err(err) = file::write_file_content(&report_file, &contradiction_report(data)) {
+            error!("{}", err);
+        }
+        if !data.contradictions.is_empty() {
+            error!(
+                "found {} contradictions, see {:?}",
+                data.contradictions.len(),
+                report_file
+            );
+            std::process::exit(1);
+        }
+    }
+
+    pub(crate) fn find_redundant(&self) {
+        if !self.args.contains(&Args::Redundant) {
+            return;
+        }
+        let data = self.get_data();
+        self.time.print("looking for redundant facts");
+        let report_file = self.paths.tmp_dir.join("redundant.md");
+        match file::write_file_content(&report_file, &redundancy_report(data)) {
+            Ok(()) => info!("redundant facts listed in {:?}", report_file),
+            Err(err) => error!("{}", err),
+        }
+    }
+
     pub(crate) fn make_dots(&self) {
         if !self.args.contains(&Args::Dots) {
             return;
@@ -122,43 +169,53 @@ impl Computation {
             .filter(|x| x.score >= self.simplified_hide_irrelevant_parameters_below)
             .collect();
         let graphs: Vec<&GraphClass> = data.graph_classes.values().collect();
-        let param_edges: Vec<(String, String)> = data
-            .arc_parameter_parameter
-            .iter()
-            .map(|(f, t, _)| (f.id.to_string(), t.id.to_string()))
-            .collect();
-        self.make_single_dot("parameters", &parameters, &param_edges);
+        let param_edges = parameter_edges(data);
+        let mut param_labels: HashMap<String, String> = HashMap::new();
+        for class in &data.equivalent_parameters {
+            let names: Vec<String> = class.iter().map(|x| x.name_core.name.clone()).collect();
+            for parameter in class {
+                param_labels.insert(parameter.id.to_string(), names.join(", "));
+            }
+        }
+        let param_style = |f: &str, t: &str| parameter_edge_style(data, f, t);
+        self.make_single_dot("parameters", &parameters, &param_edges, &param_labels, param_style);
         self.make_single_dot(
             "parameters_simplified",
             &simplified_parameters,
             &param_edges,
+            &param_labels,
+            param_style,
         );
         let gc_edges: Vec<(String, String)> = data
             .arc_gc_gc
             .iter()
-            .map(|(f, t, _)| (f.id.to_string(), t.id.to_string()))
+            .flat_map(|(f, t, inclusion)| {
+                let (f, t) = (f.id.to_string(), t.id.to_string());
+                match inclusion.relation {
+                    ImplicationRelation::Implies => vec![(f, t)],
+                    ImplicationRelation::Equivalent => vec![(f.clone(), t.clone()), (t, f)],
+                    ImplicationRelation::Excludes | ImplicationRelation::Unknown => vec![],
+                }
+            })
             .collect();
-        self.make_single_dot("graphs", &graphs, &gc_edges);
+        let gc_style = |f: &str, t: &str| {
+            knowledge_edge_style(data.graph_class_knowledge(&PreviewGraphClassId::from(f), &PreviewGraphClassId::from(t)))
+        };
+        self.make_single_dot("graphs", &graphs, &gc_edges, &HashMap::new(), gc_style);
     }
 
-    fn make_single_dot<T>(&self, name: &str, items: &[&T], edges: &[(String, String)])
-    where
-        T: Named + HasId,
+    /// Saves the Hasse diagram of the items among the html files, see `hasse_diagram`.
+    fn make_single_dot<T>(
+        &self,
+        name: &str,
+        items: &[&T],
+        edges: &[(String, String)],
+        labels: &HashMap<String, String>,
+        style: impl Fn(&str, &str) -> HashSet<DotEdgeAttribute>,
+    ) where
+        T: Named + HasId + Score,
     {
-        let mut digraph: DotGraph<T> = DotGraph::new(name, None);
-        for dp in items {
-            digraph.add_vertex(*dp);
-        }
-        let displayed_ids: HashSet<String> = items.iter().map(|x| x.id()).collect();
-        for (f, t) in edges {
-            if displayed_ids.contains(f) && displayed_ids.contains(t) {
-                digraph.add_edge(DotEdge {
-                    from: f.clone(),
-                    to: t.clone(),
-                    data: HashSet::new(),
-                });
-            }
-        }
+        let digraph = hasse_diagram(name, items, edges, labels, style);
         if let Ok(done_dot) = digraph.save_to_file(&self.paths.working_dir) {
             let final_dot = self.paths.html_dir.join(format!("{}.dot", name));
             info!("copy dot to {:?}", &final_dot);
@@ -177,6 +234,8 @@ impl Computation {
         // data.sets.iter().map(|x|api_data.push(Box::new(x)));
         api::create_set_api(data, &self.paths.api_dir)?;
         api::create_simple_api(data, &self.paths.api_dir)?;
+        api::create_relation_api(data, &self.paths.api_dir)?;
+        api::create_problem_api(data, &self.paths.api_dir)?;
         Ok(())
     }
 
@@ -200,11 +259,19 @@ impl Computation {
             parametric_parameters,
             providers,
             tags,
+            problems,
             sources,
             sorted_sources,
             arc_parameter_parameter,
+            partial_results,
+            equivalent_parameters,
+            pair_classes,
+            knowledge_parameter_parameter,
+            knowledge_gc_gc,
+            contradictions,
             arc_lf_lf,
             arc_op_op,
+            arc_grrel_grrel,
             arc_graph_graph,
             arc_gc_gc,
             arc_graph_gc,
@@ -217,6 +284,7 @@ impl Computation {
             arc_problem_gcprop,
             arc_problem_parameter,
             arc_gcprop_parameter,
+            relation_index: _,
         } = data;
         fn add_to_links<IdT, T>(
             items: &HashMap<PreviewId<IdT>, T>,
@@ -241,6 +309,7 @@ impl Computation {
         add_to_links(providers, &mut links);
         add_to_links(graph_relations, &mut links);
         add_to_links(tags, &mut links);
+        add_to_links(problems, &mut links);
         fn add_to_content<'a, I, T>(
             items: &'a HashMap<PreviewId<I>, T>,
             paths: &Paths,
@@ -262,6 +331,7 @@ impl Computation {
         add_to_content(parametric_graph_class, &self.paths, &mut generated_pages);
         add_to_content(providers, &self.paths, &mut generated_pages);
         add_to_content(graph_relations, &self.paths, &mut generated_pages);
+        add_to_content(problems, &self.paths, &mut generated_pages);
         let mut handcrafted_pages: HashMap<PathBuf, PathBuf> = HashMap::new();
         for source in file::iterate_folder_recursively(&self.paths.handcrafted_dir) {
             let relative = source.strip_prefix(&self.paths.handcrafted_dir).unwrap();
@@ -5,6 +5,8 @@ use std::io::Write;
 use log::{error, warn};
 
 use crate::data::data::Data;
+use crate::data::id::PreviewParameterId;
+use crate::work::explain::{ProofStyle, explain_relation};
 
 pub(super) fn process_command(data: &Data, mut command: LinkedList<String>) -> bool {
     if let Some(cmd) = command.pop_front() {
@@ -14,6 +16,12 @@ pub(super) fn process_command(data: &Data, mut command: LinkedList<String>) -> b
                 println!(
                     "    hasse <par_id> [<par_id> ...] - draws hasse diagram of the listed parameters"
                 );
+                println!(
+                    "    explain <par_id> <par_id> - shows how the relation of the two parameters was derived"
+                );
+                println!(
+                    "    relation <par_id> <par_id> - shows the relation of the two parameters in both directions"
+                );
                 println!("    exit - end the interactive prompt");
             }
             // "hasse" => {
@@ -39,6 +47,33 @@ pub(super) fn process_command(data: &Data, mut command: LinkedList<String>) -> b
             //         println!("pdf generated at '{:?}'", pdf_target_file);
             //     }
             // }
+            "explain" => {
+                let (Some(subset), Some(superset)) = (command.pop_front(), command.pop_front())
+                else {
+                    warn!("explain expects two parameter ids");
+                    return true;
+                };
+                let subset = PreviewParameterId::from(subset.as_str());
+                let superset = PreviewParameterId::from(superset.as_str());
+                let proofs = explain_relation(data, &subset, &superset);
+                if proofs.is_empty() {
+                    println!("relation from {} to {} is unknown", subset, superset);
+                }
+                for proof in proofs {
+                    print!("{}", proof.format(data, ProofStyle::Plain));
+                }
+            }
+            "relation" => {
+                let (Some(a), Some(b)) = (command.pop_front(), command.pop_front()) else {
+                    warn!("relation expects two parameter ids");
+                    return true;
+                };
+                let a = PreviewParameterId::from(a.as_str());
+                let b = PreviewParameterId::from(b.as_str());
+                let relation = data.relation(&a, &b);
+                println!("{} to {}: {:?}", a, b, relation.forward_cpx());
+                println!("{} to {}: {:?}", b, a, relation.backward_cpx());
+            }
             "exit" => return false,
             x => warn!("unknown command '{}'", x),
         }
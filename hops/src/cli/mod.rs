@@ -17,6 +17,7 @@ pub(crate) enum Args {
     Api,
     Clear,
     Interactive,
+    Redundant,
     Debug,
     Trace,
 }
@@ -67,6 +68,9 @@ pub(crate) fn parse_args_and_init_logger() -> HashSet<Args> {
                 args.insert(Args::Api);
                 args.insert(Args::Table);
             }
+            "redundant" => {
+                args.insert(Args::Redundant);
+            }
             "interactive" | "i" => {
                 args.insert(Args::Interactive);
             }
@@ -15,7 +15,7 @@ use crate::input::raw_enums::{
 use crate::input::raw_enums::{RawOwn::*, *};
 use crate::input::source::{
     Cpx::*, ImplicationRelation, InclusionRelationUnderGraphRelation, RawNotedSource,
-    RawWroteStatus::*, definition, relation,
+    RawWroteStatus::*, bound, definition, relation,
 };
 
 pub fn build_collection() -> RawData {
@@ -51,7 +51,7 @@ pub fn build_collection() -> RawData {
     let logic_fo = create.logic_fragment("cGKOIy", "First-order logic on graphs", None);
     let logic_mso = create.logic_fragment("XF5B1J", "Monadic second-order logic on graphs", None);
 
-    let g_subgraph = create.graph_relation_type("yVyItw", "subgraph", GrRel::IsomorphicAfterOperations(copyvec(vec![&op_vtx_deletion, &op_edge_deletion])));
+    let g_subgraph = create.graph_relation_type(SUBGRAPH_RELATION_ID, "subgraph", GrRel::IsomorphicAfterOperations(copyvec(vec![&op_vtx_deletion, &op_edge_deletion])));
     let g_minor = create.graph_relation_type("Mnayro", "minor", GrRel::IsomorphicAfterOperations(copyvec(vec![&op_vtx_deletion, &op_edge_deletion, &op_edge_contraction])),
     );
     let g_topological_minor = create.graph_relation_type("8i8TPb", "topological minor", GrRel::IsomorphicAfterOperations(copyvec(vec![&op_vtx_deletion, &op_edge_deletion, &op_deg2_contraction])),
@@ -891,8 +891,8 @@ pub fn build_collection() -> RawData {
     let chordality1993 = source("IFY0Rw", "chordality1993", 4)
         .wrote(Pp(1), "The \\emph{chordality} of a graph $G=(V,E)$ is defined as the minimum $k$ such that we can write $E=E_1,\\cap\\dots\\cap E_k$ with each $(V,E_i)$ a chordal graph.", vec![("Xdg7Hv", Derivative, definition(&chordality))])
         .wrote(Pp(2), "Corollary 3. For any graph $G$, $\\mathrm{Chord}(G) \\le |V(G)|/2$.", vec![("D5VlqV", Original, relation(&size, &chordality, UpperBound(Linear)))])
-        .wrote(Pp(2), "Corollary 4. For any graph $G$, $\\mathrm{Chord}(G) \\le \\chi(G)$, the chromatic number of $G$.", vec![("rQBO3K", Original, relation(&chromatic_number, &chordality, UpperBound(Linear)))])
-        .wrote(Pp(5), "Theorem 7. For any graph $G$, $\\mathrm{Chord}(G) \\le \\tau(G)$.", vec![("N0jfjr", Original, relation(&treewidth, &chordality, UpperBound(Linear)))])
+        .wrote(Pp(2), "Corollary 4. For any graph $G$, $\\mathrm{Chord}(G) \\le \\chi(G)$, the chromatic number of $G$.", vec![("rQBO3K", Original, relation(&chromatic_number, &chordality, UpperBoundBy(bound("k"))))])
+        .wrote(Pp(5), "Theorem 7. For any graph $G$, $\\mathrm{Chord}(G) \\le \\tau(G)$.", vec![("N0jfjr", Original, relation(&treewidth, &chordality, UpperBoundBy(bound("k"))))])
         .done(&mut create);
     let malitz1994 = source("cCrsoK", "Malitz1994", 2)
         .wrote(Pp(24), "Theorem 5.1. Genus $g$ graphs have pagenumber $O(\\sqrt{g})$.", vec![
@@ -961,7 +961,7 @@ pub fn build_collection() -> RawData {
         .done(&mut create);
     let courcelle_olariu_2000 = source("ZQrXS8", "courcelle2000", 5)
         // .defined("OL0McK", PageTodo, &clique_width, "")
-        .wrote(Pp(18), "We will prove that for every undirected graph $G$, $cwd(G) \\le 2^{twd(G)+1}+1$ ...", vec![("sGBrPC", Original, relation(&treewidth, &clique_width, UpperBound(Exponential)))])
+        .wrote(Pp(18), "We will prove that for every undirected graph $G$, $cwd(G) \\le 2^{twd(G)+1}+1$ ...", vec![("sGBrPC", Original, relation(&treewidth, &clique_width, UpperBoundBy(bound("2^(k+1)+1"))))])
         .done(&mut create);
     let tack_layouts2004 = source("w7RVn9", "TackLayouts2004", 3)
         // .defined("bcdAXe", Pp(2), &track_number, "The track-number of $G$ is $\\mathrm{tn}_1(G)$, ...")
@@ -1026,7 +1026,7 @@ pub fn build_collection() -> RawData {
         // d_path_free
         .todo_rest(&mut create);
     let cliquewidthnpc2009 = source("zuhSo5", "cliquewidthnpc2009", 2)
-        .wrote(Pp(8), "(5) $\\mathrm{lin-cwd}(G) \\le \\mathrm{pwd}(G)+2$.", vec![("i1eBMN", Original, relation(&pathwidth, &linear_clique_width, UpperBound(Linear)))])
+        .wrote(Pp(8), "(5) $\\mathrm{lin-cwd}(G) \\le \\mathrm{pwd}(G)+2$.", vec![("i1eBMN", Original, relation(&pathwidth, &linear_clique_width, UpperBoundBy(bound("k+2"))))])
         .todo_rest(&mut create);
     let wood_partition2009 = source("p00uyg", "WoodPartition2009", 3)
         .wrote(Pp(1), "A graph $H$ is a partition of a graph $G$ if: each vertex of $H$ is a set of vertices of $G$ (called a bag), every evrtex of $G$ is in exactly one bag of $H$, and distinct bags $A$ and $B$ are adjacent in $H$ if and only if there is an edge of $G$ with one endpoint in $A$ and the other endpoint in $B$. The width of a partition is the maximum number of vertices in a bag. ... If a forest $T$ is a partition of a graph $G$, then $T$ is a tree-partition of $G$. The tree-partition-width of $G$ ... is the minimum width of a tree-partition of $G$.", vec![("AKfiZY", Derivative, definition(&tree_partition_width))])
@@ -1101,7 +1101,7 @@ pub fn build_collection() -> RawData {
         .wrote(PageTodo, "Observation 1. Let $G$ be a graph. Then $cw(G) \\ge \\Delta(G)$.", vec![("ZHXKjC", Original, relation(&carving_width, &maximum_degree, UpperBound(Linear)))])
         .todo_rest(&mut create);
     let jansen2013 = source("FLOjic", "Jansen2013", 1)
-        // .hasse("u6oAPX", Pp(46), copyvec(vec![&vertex_cover, &maximum_leaf_num, &dist_to_complete, &dist_to_linear_forest, &cutwidth, &bandwidth, &topological_bandwidth, &feedback_vertex_set, &dist_to_chordal, &dist_to_outerplanar, &pathwidth, &odd_cycle_transversal, &treewidth, &genus, &dist_to_perfect, &chromatic_number]))
+        .hasse("u6oAPX", Pp(46), copyvec(vec![&vertex_cover, &maximum_leaf_num, &dist_to_complete, &dist_to_linear_forest, &cutwidth, &bandwidth, &topological_bandwidth, &feedback_vertex_set, &dist_to_chordal, &dist_to_outerplanar, &pathwidth, &odd_cycle_transversal, &treewidth, &genus, &dist_to_perfect, &chromatic_number]))
         .wrote(PageTodo, "The \\emph{topological bandwidth} of a graph $G$ is the minimum [bandwidth](../aP5a38) over all subdivisions of $G$", vec![("PV6tGG", Original, definition(&topological_bandwidth))])
         .todo_rest(&mut create);
     let treecutwidth2015 = source("zbWWC6", "treecutwidth2015", 3).todo_rest(&mut create);
@@ -1110,7 +1110,7 @@ pub fn build_collection() -> RawData {
             ("3yUfrd", Original, relation(&linear_rank_width, &linear_clique_width, UpperBound(Exists))),
             ("2dN9wh", Original, relation(&linear_clique_width, &linear_rank_width, UpperBound(Exists))),
         ])
-        .wrote(Pp(3), "Lemma 5. Any graph $G$ satisfies $\\mathrm{lrw}(G) \\le \\mathrm{pw}(G)$.", vec![("dvqfqQ", Original, relation(&pathwidth, &linear_rank_width, UpperBound(Linear)))])
+        .wrote(Pp(3), "Lemma 5. Any graph $G$ satisfies $\\mathrm{lrw}(G) \\le \\mathrm{pw}(G)$.", vec![("dvqfqQ", Original, relation(&pathwidth, &linear_rank_width, UpperBoundBy(bound("k"))))])
         .todo_rest(&mut create);
     let twin_cover_2015 = source("VQLE2i", "ganianTwinCover2015", 4)
         .wrote(Pp(5), "Definition 3 A set of vertices $X \\subseteq V(G)$ is a twin-cover of $G$ if for every edge $e = ab \\in E(G)$ either 1. $a \\in X$ or $b \\in X$, or 2. $a$ and $b$ are true twins. We then say that $G$ has twin-cover $k$ if the size of a minimum twin-cover of $G$ is $k$.", vec![("J1sHj8", Original, definition(&twin_cover_num))])
@@ -1206,8 +1206,8 @@ pub fn build_collection() -> RawData {
     let schroder_thesis = source("DYGiYb", "SchroderThesis", 7)
         // // .cited("pJxHVS", PageTodo, sorge2019, "Based on the work by [Sa19] as well as [Fr8], we investigate PageTodo connections between graph parameters to continue the work on the graph parameter hierarchy")
         // // .cited("bybFgo", PageTodo, froemmrich2018, "Based on the work by [Sa19] as well as [Fr8], we investigate PageTodo connections between graph parameters to continue the work on the graph parameter hierarchy")
-        // .hasse("DfHlFn", Pp(7), schroder_parameter_list.clone())
-        // .table("ONqedT", Pp(8), schroder_parameter_list.clone())
+        .hasse("DfHlFn", Pp(7), schroder_parameter_list.clone())
+        .table("ONqedT", Pp(8), schroder_parameter_list.clone())
         // .proved("R9eI61", Pp(11), &treedepth, &diameter, UpperBound(Exponential), "Proposition 3.1")
         // .proved("dohKmq", Pp(12), &dist_to_linear_forest, &hindex, UpperBound(Linear), "Proposition 3.2")
         // .proved("WY0T4I", Pp(13), &dist_to_cluster, &dist_to_co_cluster, Exclusion, "Proposition 3.3")
@@ -1336,7 +1336,7 @@ pub fn build_collection() -> RawData {
         // .proved("gvSCeQ", PageTodo, &inf_flip_width, &r_flip_width, UpperBound(Linear), by_definition)
         .todo_rest(&mut create);
     let slim_tcw2024 = source("7g1aTu", "SlimTCW2024", 5)
-        // .hasse("hROdkf", Pp(2716), copyvec(vec![&tree_cut_width, &slim_tree_cut_width, &degree_treewidth, &feedback_edge_set])) // also tcw0, tcw1, tcw2, edge-cut width
+        .hasse("hROdkf", Pp(2716), copyvec(vec![&tree_cut_width, &slim_tree_cut_width, &degree_treewidth, &feedback_edge_set])) // also tcw0, tcw1, tcw2, edge-cut width
         // .collective(Pp(2716), "Figure 1")
         // .noted_relation("GreUNa", &slim_tree_cut_width, &tree_cut_width, UpperBound(Exists), SrcTodo)
         // .noted_relation("9TXlI9", &edge_cut_width, &slim_tree_cut_width, UpperBound(Exists), SrcTodo)
@@ -1369,7 +1369,7 @@ pub fn build_collection() -> RawData {
         // .proved("Lh05uc", Pp(3), &iterated_type_partitions, &modular_width, UpperBound(Linear), by_definition)
         .todo_rest(&mut create);
     let treebandwidth2025 = source("EImlRb", "treebandwidth2025", 4)
-        // .hasse("l1oyAq", Pp(36), copyvec(vec![&size, &vertex_cover, &component_size, &treedepth, &pathwidth, &feedback_vertex_set, &treewidth, &maximum_degree, &cutwidth, &domino_treewidth, &treebandwidth, &tree_partition_width, &slim_tree_cut_width, &edge_treewidth, &tree_cut_width, &overlap_treewidth])) // also: fan number, dipole number, biconnected maximum degree
+        .hasse("l1oyAq", Pp(36), copyvec(vec![&size, &vertex_cover, &component_size, &treedepth, &pathwidth, &feedback_vertex_set, &treewidth, &maximum_degree, &cutwidth, &domino_treewidth, &treebandwidth, &tree_partition_width, &slim_tree_cut_width, &edge_treewidth, &tree_cut_width, &overlap_treewidth])) // also: fan number, dipole number, biconnected maximum degree
         // .defined("u2JciR", Pp(1), &treebandwidth, "A \\emph{tree-layout} of $G=(V,E)$ is a rooted tree $T$ whose nodes are the vertices of $V$, and such that, for every edge $xy \\in E$, $x$ is an ancestor of $y$ or vice-versa. The bandwidth of $T$ is then the maximum distance in $T$ between pairs of neighbors in $G$. We call \\emph{treebandwidth} of $G$, the minimum bandwidth over tree-layouts of $G$, and denote it by ${\\rm tbw}(G)$.")
         // // Pp(5), relation to graphclasses
         // .proved("tInJY1", Pp(7), &tree_partition_width, &treebandwidth, StrictUpperBound(Linear), "By rooting the tree-partition arbitrarily and replacing each bag by an arbitrary linear ordering of its vertices one derives ${\\rm tbw}(G) \\le 2 \\cdot {\\rm tpw}(G)$. However, some graphs of treebandwidth 2 have unbounded tree-partition-width: ...")
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
-use biblatex::{Bibliography, Chunk, DateValue, PermissiveType, Spanned};
+use biblatex::{Bibliography, Chunk, Spanned};
 use log::error;
 
 use crate::{
@@ -54,27 +54,7 @@ pub fn bibligraphy_to_source(
                     None => name = Some(authors),
                 };
             }
-            if let Ok(dt) = e.date() {
-                match dt {
-                    PermissiveType::Typed(t) => match t.value {
-                        DateValue::At(d) => {
-                            date.year = Some(d.year);
-                        }
-                        DateValue::After(d) => {
-                            panic!("unknown date type")
-                        }
-                        DateValue::Before(d) => {
-                            panic!("unknown date type")
-                        }
-                        DateValue::Between(s, e) => {
-                            panic!("unknown date type")
-                        }
-                    },
-                    PermissiveType::Chunks(chunks) => {
-                        panic!("unknown date type")
-                    }
-                }
-            }
+            date = Date::from(e);
             Some(e.to_biblatex_string())
         });
     }
@@ -0,0 +1,128 @@
+//! Dense square boolean matrix stored as rows of 64 bit words.
+
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    size: usize,
+    words: usize,
+    bits: Vec<u64>,
+}
+
+impl BitMatrix {
+    pub fn new(size: usize) -> Self {
+        let words = size.div_ceil(64);
+        Self {
+            size,
+            words,
+            bits: vec![0; size * words],
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.bits[row * self.words + col / 64] & (1 << (col % 64)) != 0
+    }
+
+    pub fn set(&mut self, row: usize, col: usize) {
+        self.bits[row * self.words + col / 64] |= 1 << (col % 64);
+    }
+
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.bits[row * self.words..(row + 1) * self.words]
+    }
+
+    /// Columns set in the row.
+    pub fn ones(&self, row: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row(row).iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    /// The lowest column set in both rows, rows are given as words.
+    pub fn first_common(a: &[u64], b: &[u64]) -> Option<usize> {
+        a.iter()
+            .zip(b)
+            .enumerate()
+            .find_map(|(i, (x, y))| match x & y {
+                0 => None,
+                common => Some(i * 64 + common.trailing_zeros() as usize),
+            })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&x| x == 0)
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut res = Self::new(self.size);
+        for row in 0..self.size {
+            for col in self.ones(row) {
+                res.set(col, row);
+            }
+        }
+        res
+    }
+
+    /// Boolean product, row `a` of the result is the union of the rows `c` of `other`
+    /// for every `c` set in row `a` of `self`. Rows are computed in parallel.
+    pub fn product(&self, other: &Self) -> Self {
+        assert_eq!(self.size, other.size);
+        let mut res = Self::new(self.size);
+        if self.words == 0 {
+            return res;
+        }
+        res.bits
+            .par_chunks_mut(self.words)
+            .enumerate()
+            .for_each(|(row, target)| {
+                for mid in self.ones(row) {
+                    for (t, s) in target.iter_mut().zip(other.row(mid)) {
+                        *t |= s;
+                    }
+                }
+            });
+        res
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// Entries of `self` that are not set in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// Clears the diagonal.
+    pub fn without_diagonal(mut self) -> Self {
+        for i in 0..self.size {
+            self.bits[i * self.words + i / 64] &= !(1 << (i % 64));
+        }
+        self
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(self.size, other.size);
+        Self {
+            size: self.size,
+            words: self.words,
+            bits: self.bits.iter().zip(&other.bits).map(|(&a, &b)| f(a, b)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/bit_matrix.rs"]
+mod tests;
@@ -0,0 +1,528 @@
+//! Exact bounds between parameters such as `3·2^{k-1}`, `k log k` or `2^{O(k^2)}`.
+//!
+//! A bound is a function of the parameter `k`. Bounds compose by substitution and are
+//! compared by their asymptotic growth where constant factors are ignored on every level,
+//! so `2^k` and `2^{2k}` grow the same. The growth also gives the coarse `CpxTime`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::enums::CpxTime;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Bound {
+    Constant(i64),
+    Parameter,
+    Sum(Vec<Bound>),
+    Product(Vec<Bound>),
+    /// Bound raised to a fixed power.
+    Power(Box<Bound>, u32),
+    /// Fixed base raised to the bound.
+    Exponential(u32, Box<Bound>),
+    Logarithm(Box<Bound>),
+    /// Some function in the O-class of the bound.
+    BigO(Box<Bound>),
+}
+
+/// Asymptotic growth of a bound up to constant factors on every level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Growth {
+    /// `k^degree · log(k)^logs`, constants have both zero.
+    Polynomial { degree: u32, logs: u32 },
+    /// `2^inner` where inner grows at least logarithmically.
+    Exponential(Box<Growth>),
+}
+
+const CONSTANT: Growth = Growth::Polynomial { degree: 0, logs: 0 };
+const LOGARITHMIC: Growth = Growth::Polynomial { degree: 0, logs: 1 };
+
+impl Growth {
+    fn exponential(inner: Growth) -> Growth {
+        if inner == CONSTANT {
+            CONSTANT
+        } else {
+            Growth::Exponential(Box::new(inner))
+        }
+    }
+
+    fn logarithm(self) -> Growth {
+        match self {
+            Growth::Polynomial { degree: 0, logs: 0 } => CONSTANT,
+            // iterated logarithms are bounded from above by the logarithm
+            Growth::Polynomial { .. } => LOGARITHMIC,
+            Growth::Exponential(inner) => *inner,
+        }
+    }
+
+    fn product(self, other: Growth) -> Growth {
+        match (self, other) {
+            (Growth::Polynomial { degree: da, logs: la }, Growth::Polynomial { degree: db, logs: lb }) => {
+                Growth::Polynomial {
+                    degree: da + db,
+                    logs: la + lb,
+                }
+            }
+            // the logarithm of a polynomial is at most the logarithmic exponent
+            (Growth::Exponential(inner), Growth::Polynomial { .. })
+            | (Growth::Polynomial { .. }, Growth::Exponential(inner)) => Growth::Exponential(inner),
+            (Growth::Exponential(a), Growth::Exponential(b)) => Growth::Exponential(Box::new(a.max(*b))),
+        }
+    }
+
+    fn power(self, exponent: u32) -> Growth {
+        match self {
+            Growth::Polynomial { degree, logs } => Growth::Polynomial {
+                degree: degree * exponent,
+                logs: logs * exponent,
+            },
+            exponential if exponent > 0 => exponential,
+            _ => CONSTANT,
+        }
+    }
+
+    /// The larger of the two, taking the exponential one if they are incomparable.
+    fn max(self, other: Growth) -> Growth {
+        match self.partial_cmp(&other) {
+            Some(Ordering::Less) => other,
+            Some(_) => self,
+            None if matches!(other, Growth::Exponential(_)) => other,
+            None => self,
+        }
+    }
+
+    pub fn time(&self) -> CpxTime {
+        match self {
+            Growth::Polynomial { degree: 0, logs: 0 } => CpxTime::Constant,
+            Growth::Polynomial { degree: 0 | 1, logs: 0 } | Growth::Polynomial { degree: 0, .. } => CpxTime::Linear,
+            Growth::Polynomial { .. } => CpxTime::Polynomial,
+            Growth::Exponential(inner) => match inner.as_ref() {
+                Growth::Polynomial { degree: 0, logs: 1 } => CpxTime::Polynomial,
+                Growth::Polynomial { degree: 0, .. } | Growth::Polynomial { degree: 1, logs: 0 } => {
+                    CpxTime::Exponential
+                }
+                _ => CpxTime::Tower,
+            },
+        }
+    }
+}
+
+impl PartialOrd for Growth {
+    /// Exponentials of a logarithm are polynomials of an unknown degree so they are
+    /// incomparable with other polynomials.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Growth::Polynomial { degree: da, logs: la }, Growth::Polynomial { degree: db, logs: lb }) => {
+                Some((da, la).cmp(&(db, lb)))
+            }
+            (Growth::Exponential(a), Growth::Exponential(b)) => a.partial_cmp(b),
+            (Growth::Exponential(inner), Growth::Polynomial { degree, .. }) => {
+                if **inner == LOGARITHMIC && *degree > 0 {
+                    None
+                } else {
+                    Some(Ordering::Greater)
+                }
+            }
+            (Growth::Polynomial { .. }, Growth::Exponential(_)) => other.partial_cmp(self).map(Ordering::reverse),
+        }
+    }
+}
+
+impl Bound {
+    pub fn growth(&self) -> Growth {
+        match self {
+            Bound::Constant(_) => CONSTANT,
+            Bound::Parameter => Growth::Polynomial { degree: 1, logs: 0 },
+            Bound::Sum(terms) => terms.iter().map(Bound::growth).fold(CONSTANT, Growth::max),
+            Bound::Product(factors) => factors.iter().map(Bound::growth).fold(CONSTANT, Growth::product),
+            Bound::Power(base, exponent) => base.growth().power(*exponent),
+            Bound::Exponential(0 | 1, _) => CONSTANT,
+            Bound::Exponential(_, exponent) => Growth::exponential(exponent.growth()),
+            Bound::Logarithm(inner) => inner.growth().logarithm(),
+            Bound::BigO(inner) => inner.growth(),
+        }
+    }
+
+    /// The coarse complexity class of the bound.
+    pub fn time(&self) -> CpxTime {
+        self.growth().time()
+    }
+
+    /// Asymptotic comparison, `None` if the growths are incomparable.
+    pub fn compare(&self, other: &Bound) -> Option<Ordering> {
+        self.growth().partial_cmp(&other.growth())
+    }
+
+    /// The bound `self(inner(k))`.
+    pub fn compose(&self, inner: &Bound) -> Bound {
+        match self {
+            Bound::Constant(c) => Bound::Constant(*c),
+            Bound::Parameter => inner.clone(),
+            Bound::Sum(terms) => Bound::Sum(terms.iter().map(|x| x.compose(inner)).collect()),
+            Bound::Product(factors) => Bound::Product(factors.iter().map(|x| x.compose(inner)).collect()),
+            Bound::Power(base, exponent) => Bound::Power(Box::new(base.compose(inner)), *exponent),
+            Bound::Exponential(base, exponent) => Bound::Exponential(*base, Box::new(exponent.compose(inner))),
+            Bound::Logarithm(x) => Bound::Logarithm(Box::new(x.compose(inner))),
+            Bound::BigO(x) => Bound::BigO(Box::new(x.compose(inner))),
+        }
+        .simplify()
+    }
+
+    /// Flattens nested sums and products and folds the constants.
+    pub fn simplify(self) -> Bound {
+        match self {
+            Bound::Sum(terms) => {
+                let mut constant = 0i64;
+                let mut rest = vec![];
+                let terms = terms.into_iter().map(Bound::simplify).flat_map(|term| match term {
+                    Bound::Sum(inner) => inner,
+                    term => vec![term],
+                });
+                for term in terms {
+                    match term {
+                        Bound::Constant(c) => constant = constant.saturating_add(c),
+                        term => rest.push(term),
+                    }
+                }
+                if constant != 0 || rest.is_empty() {
+                    rest.push(Bound::Constant(constant));
+                }
+                Self::single_or(rest, Bound::Sum)
+            }
+            Bound::Product(factors) => {
+                let mut constant = 1i64;
+                let mut rest = vec![];
+                let factors = factors.into_iter().map(Bound::simplify).flat_map(|factor| match factor {
+                    Bound::Product(inner) => inner,
+                    factor => vec![factor],
+                });
+                for factor in factors {
+                    match factor {
+                        Bound::Constant(c) => constant = constant.saturating_mul(c),
+                        factor => rest.push(factor),
+                    }
+                }
+                if constant == 0 {
+                    return Bound::Constant(0);
+                }
+                if constant != 1 || rest.is_empty() {
+                    rest.insert(0, Bound::Constant(constant));
+                }
+                Self::single_or(rest, Bound::Product)
+            }
+            Bound::Power(base, exponent) => match (base.simplify(), exponent) {
+                (_, 0) => Bound::Constant(1),
+                (base, 1) => base,
+                (Bound::Constant(c), e) if c.checked_pow(e).is_some() => Bound::Constant(c.pow(e)),
+                (Bound::Power(base, a), b) => Bound::Power(base, a * b),
+                (base, e) => Bound::Power(Box::new(base), e),
+            },
+            Bound::Exponential(base, exponent) => match exponent.simplify() {
+                Bound::Constant(c) if (0..32).contains(&c) && (base as i64).checked_pow(c as u32).is_some() => {
+                    Bound::Constant((base as i64).pow(c as u32))
+                }
+                exponent => Bound::Exponential(base, Box::new(exponent)),
+            },
+            Bound::Logarithm(inner) => Bound::Logarithm(Box::new(inner.simplify())),
+            Bound::BigO(inner) => match inner.simplify() {
+                Bound::BigO(inner) => Bound::BigO(inner),
+                // constant factors do not matter in the O-notation
+                Bound::Product(factors) => {
+                    let factors: Vec<Bound> = factors.into_iter().filter(|x| !matches!(x, Bound::Constant(_))).collect();
+                    Bound::BigO(Box::new(Self::single_or(factors, Bound::Product)))
+                }
+                Bound::Constant(_) => Bound::BigO(Box::new(Bound::Constant(1))),
+                inner => Bound::BigO(Box::new(inner)),
+            },
+            bound => bound,
+        }
+    }
+
+    fn single_or(mut items: Vec<Bound>, make: fn(Vec<Bound>) -> Bound) -> Bound {
+        match items.len() {
+            0 => Bound::Constant(1),
+            1 => items.pop().unwrap(),
+            _ => make(items),
+        }
+    }
+
+    /// Formats the bound as LaTeX math.
+    pub fn to_latex(&self) -> String {
+        let mut res = String::new();
+        self.write(&mut res, Notation::Latex, 0);
+        res
+    }
+
+    fn write(&self, res: &mut String, notation: Notation, precedence: u8) {
+        let parenthesize = precedence > self.precedence();
+        if parenthesize {
+            res.push('(');
+        }
+        match self {
+            Bound::Constant(c) => res.push_str(&c.to_string()),
+            Bound::Parameter => res.push('k'),
+            Bound::Sum(terms) => {
+                for (i, term) in terms.iter().enumerate() {
+                    match term {
+                        Bound::Constant(c) if i > 0 && *c < 0 => res.push_str(&format!(" - {}", -c)),
+                        term => {
+                            if i > 0 {
+                                res.push_str(" + ");
+                            }
+                            term.write(res, notation, 1);
+                        }
+                    }
+                }
+            }
+            Bound::Product(factors) => {
+                for (i, factor) in factors.iter().enumerate() {
+                    if i > 0 {
+                        res.push_str(match factor {
+                            Bound::Logarithm(_) => " ",
+                            _ => notation.times(),
+                        });
+                    }
+                    factor.write(res, notation, 2);
+                }
+            }
+            Bound::Power(base, exponent) => {
+                base.write(res, notation, 4);
+                match notation {
+                    Notation::Plain => res.push_str(&format!("^{}", exponent)),
+                    Notation::Latex => res.push_str(&format!("^{{{}}}", exponent)),
+                }
+            }
+            Bound::Exponential(base, exponent) => {
+                res.push_str(&format!("{}^", base));
+                match notation {
+                    Notation::Plain => exponent.write(res, notation, 4),
+                    Notation::Latex => {
+                        res.push('{');
+                        exponent.write(res, notation, 0);
+                        res.push('}');
+                    }
+                }
+            }
+            Bound::Logarithm(inner) => {
+                res.push_str(notation.log());
+                inner.write(res, notation, 4);
+            }
+            Bound::BigO(inner) => {
+                res.push_str("O(");
+                inner.write(res, notation, 0);
+                res.push(')');
+            }
+        }
+        if parenthesize {
+            res.push(')');
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Bound::Sum(_) => 0,
+            Bound::Product(_) => 1,
+            Bound::Logarithm(_) => 2,
+            Bound::Power(..) | Bound::Exponential(..) => 3,
+            Bound::Constant(c) if *c < 0 => 0,
+            _ => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Notation {
+    Plain,
+    Latex,
+}
+
+impl Notation {
+    fn times(self) -> &'static str {
+        match self {
+            Notation::Plain => "*",
+            Notation::Latex => " \\cdot ",
+        }
+    }
+
+    fn log(self) -> &'static str {
+        match self {
+            Notation::Plain => "log ",
+            Notation::Latex => "\\log ",
+        }
+    }
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = String::new();
+        self.write(&mut res, Notation::Plain, 0);
+        write!(f, "{}", res)
+    }
+}
+
+impl FromStr for Bound {
+    type Err = String;
+
+    /// Parses bounds like `3*2^(k-1)`, `k^2`, `O(k log k)` or `2^{O(k^2)}`, LaTeX
+    /// commands `\cdot` and `\log` are accepted as well.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+        let res = parser.sum()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(res.simplify()),
+            Some(token) => Err(format!("unexpected {:?} in bound {:?}", token, s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(i64),
+    Parameter,
+    Log,
+    BigO,
+    Plus,
+    Minus,
+    Times,
+    Caret,
+    Open,
+    Close,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut res = vec![];
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() {
+            let mut number = String::new();
+            while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                number.push(d);
+                chars.next();
+            }
+            res.push(Token::Number(number.parse().map_err(|_| format!("number {} is too large", number))?));
+            continue;
+        }
+        if c.is_alphabetic() || c == '\\' {
+            let mut word = String::new();
+            chars.next();
+            if c != '\\' {
+                word.push(c);
+            }
+            while let Some(&d) = chars.peek().filter(|d| d.is_alphabetic()) {
+                word.push(d);
+                chars.next();
+            }
+            res.push(match word.as_str() {
+                "k" => Token::Parameter,
+                "log" => Token::Log,
+                "O" => Token::BigO,
+                "cdot" => Token::Times,
+                _ => return Err(format!("unknown word {:?} in bound {:?}", word, s)),
+            });
+            continue;
+        }
+        chars.next();
+        res.push(match c {
+            ' ' => continue,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' | '·' => Token::Times,
+            '^' => Token::Caret,
+            '(' | '{' => Token::Open,
+            ')' | '}' => Token::Close,
+            _ => return Err(format!("unexpected {:?} in bound {:?}", c, s)),
+        });
+    }
+    Ok(res)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let res = self.peek() == Some(token);
+        if res {
+            self.position += 1;
+        }
+        res
+    }
+
+    fn sum(&mut self) -> Result<Bound, String> {
+        let mut terms = vec![self.product()?];
+        loop {
+            if self.eat(&Token::Plus) {
+                terms.push(self.product()?);
+            } else if self.eat(&Token::Minus) {
+                let term = self.product()?;
+                terms.push(Bound::Product(vec![Bound::Constant(-1), term]));
+            } else {
+                return Ok(Bound::single_or(terms, Bound::Sum));
+            }
+        }
+    }
+
+    /// Factors are multiplied explicitly or by writing them next to each other.
+    fn product(&mut self) -> Result<Bound, String> {
+        let mut factors = vec![self.power()?];
+        loop {
+            if self.eat(&Token::Times)
+                || matches!(
+                    self.peek(),
+                    Some(Token::Number(_) | Token::Parameter | Token::Log | Token::BigO | Token::Open)
+                )
+            {
+                factors.push(self.power()?);
+            } else {
+                return Ok(Bound::single_or(factors, Bound::Product));
+            }
+        }
+    }
+
+    fn power(&mut self) -> Result<Bound, String> {
+        let base = self.atom()?;
+        if !self.eat(&Token::Caret) {
+            return Ok(base);
+        }
+        let exponent = self.atom()?.simplify();
+        match (base.simplify(), exponent) {
+            (base, Bound::Constant(e)) if e >= 0 => Ok(Bound::Power(Box::new(base), e as u32)),
+            (Bound::Constant(b), exponent) if b >= 0 => Ok(Bound::Exponential(b as u32, Box::new(exponent))),
+            (base, exponent) => Err(format!("unsupported power {}^({})", base, exponent)),
+        }
+    }
+
+    fn atom(&mut self) -> Result<Bound, String> {
+        let token = self.peek().cloned().ok_or("unexpected end of bound")?;
+        self.position += 1;
+        match token {
+            Token::Number(n) => Ok(Bound::Constant(n)),
+            Token::Parameter => Ok(Bound::Parameter),
+            Token::Log => Ok(Bound::Logarithm(Box::new(self.power()?))),
+            Token::BigO => Ok(Bound::BigO(Box::new(self.atom()?))),
+            Token::Open => {
+                let res = self.sum()?;
+                if !self.eat(&Token::Close) {
+                    return Err("missing closing parenthesis".into());
+                }
+                Ok(res)
+            }
+            token => Err(format!("unexpected {:?}", token)),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/bound.rs"]
+mod tests;
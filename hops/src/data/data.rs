@@ -4,6 +4,7 @@
 use std::cmp::Eq;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::OnceLock;
 
 use log::trace;
 use serde::{Deserialize, Serialize};
@@ -14,6 +15,7 @@ use crate::data::enums::*;
 use crate::data::id::*;
 use crate::data::link::Link;
 use crate::data::preview::*;
+use crate::data::relation_index::RelationIndex;
 use crate::data::score::Score;
 use crate::input::source::ClassicalSolvability;
 use crate::input::source::Cpx;
@@ -24,6 +26,8 @@ use crate::input::source::InclusionRelationUnderGraphRelation;
 use crate::input::source::ParameterizedSolvability;
 use crate::input::source::RelKind;
 use crate::tie_data_to_previewid;
+use crate::work::contradiction::Contradiction;
+use crate::work::inference::PartialResult;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub struct NameCore {
@@ -178,6 +182,8 @@ tie_data_to_previewid!(ParametricParameter, PreviewParametricParameterId);
 pub struct ParametricGraphClass {
     pub id: ParametricGraphClassId,
     pub closed_under: PreviewGraphRelation,
+    /// Relations implied by the order of graph relations to keep the class closed as well.
+    pub also_closed_under: Vec<PreviewGraphRelation>,
 }
 data_gettable!(
     PreviewParametricGraphClassId,
@@ -196,7 +202,7 @@ pub enum ParameterDefinition {
     Intersection(Vec<PreviewParameter>),
     IntersectionParameterProperty(PreviewParameter, PreviewGraphClassProperty),
     IntersectionParameterGraphClass(PreviewParameter, PreviewGraphClass),
-    FromParametricParameter(PreviewParametricParameter),
+    FromParametricParameter(PreviewParametricParameter, Value),
 }
 
 #[named]
@@ -240,18 +246,21 @@ data_gettable!(
     graph_class_properties
 );
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum ProblemDefinition {
     Problem(String),
     ModelChecking(PreviewLogicFragment),
 }
 
 #[named]
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Problem {
     pub id: ProblemId,
     pub definition: ProblemDefinition,
+    pub timeline: Vec<(PreviewSource, Vec<Wrote>)>,
 }
+data_gettable!(PreviewProblemId, Problem, problems);
+tie_data_to_previewid!(Problem, PreviewProblemId);
 
 #[named]
 #[derive(Debug, Serialize, Deserialize)]
@@ -303,8 +312,9 @@ pub enum GraphClassRelationDefinition {
 // );
 // tie_data_to_previewid!(GraphClassRelation, PreviewGraphClassRelationId);
 
+/// How well established a relation is, derived from the status of the facts behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum KnowledgeState {
-    // todo - check whether these are all the states
     UnknownToHOPS,
     Conjectured,
     Mentioned,
@@ -320,7 +330,7 @@ pub enum KnowledgeState {
 pub struct Source {
     pub id: SourceId,
     pub sourcekey: SourceKey,
-    pub wrote: Vec<PreviewWrote>,
+    pub wrote: Vec<Wrote>,
     pub time: Date,
     pub drawings: Vec<Drawing>,
 }
@@ -406,6 +416,32 @@ pub enum Fact {
     Relation(Relation),
 }
 
+/// Tells where a result about the complexity of a problem comes from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SolvabilityOrigin {
+    Directly(PreviewSource),
+    /// Result on `parameter` stated by `source` carried over the bound
+    /// saved at index `bound` of 'data.partial_results'.
+    AlongParameterBound {
+        parameter: PreviewParameter,
+        source: PreviewSource,
+        bound: usize,
+    },
+    /// Result on `property` stated by `source` carried over the chain of `inclusions`.
+    AlongInclusions {
+        property: PreviewGraphClassProperty,
+        source: PreviewSource,
+        inclusions: Vec<Relation>,
+    },
+}
+
+/// A complexity result of a problem together with its origin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourcedSolvability<T> {
+    pub solvability: T,
+    pub origin: SolvabilityOrigin,
+}
+
 impl Relation {
     pub fn kind(&self) -> RelKind {
         match self {
@@ -430,22 +466,43 @@ impl Relation {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Data {
+    #[serde(with = "id_map")]
     pub graph_classes: HashMap<PreviewGraphClassId, GraphClass>,
+    #[serde(with = "id_map")]
     pub graph_relations: HashMap<PreviewGraphRelationId, GraphRelation>,
+    #[serde(with = "id_map")]
     pub graph_class_properties: HashMap<PreviewGraphClassPropertyId, GraphClassProperty>,
+    #[serde(with = "id_map")]
     pub graphs: HashMap<PreviewGraphId, Graph>,
+    #[serde(with = "id_map")]
     pub logic_fragments: HashMap<PreviewLogicFragmentId, LogicFragment>,
+    #[serde(with = "id_map")]
     pub operations: HashMap<PreviewOperationId, Operation>,
+    #[serde(with = "id_map")]
     pub parameters: HashMap<PreviewParameterId, Parameter>,
+    #[serde(with = "id_map")]
     pub parametric_graph_class: HashMap<PreviewParametricGraphClassId, ParametricGraphClass>,
+    #[serde(with = "id_map")]
     pub parametric_parameters: HashMap<PreviewParametricParameterId, ParametricParameter>,
+    #[serde(with = "id_map")]
     pub providers: HashMap<PreviewProviderId, Provider>,
+    #[serde(with = "id_map")]
     pub tags: HashMap<PreviewTagId, Tag>,
+    #[serde(with = "id_map")]
+    pub problems: HashMap<PreviewProblemId, Problem>,
+    #[serde(with = "id_map")]
     pub sources: HashMap<PreviewSourceId, Source>,
     pub sorted_sources: Vec<PreviewSourceId>,
-    pub arc_parameter_parameter: Vec<(PreviewParameter, PreviewParameter, Cpx)>,
+    pub arc_parameter_parameter: Vec<(PreviewParameter, PreviewParameter, SourcedCpxInfo)>,
+    pub partial_results: Vec<PartialResult>,
+    pub equivalent_parameters: Vec<Vec<PreviewParameter>>,
+    pub pair_classes: Vec<(PreviewParameter, PreviewParameter, PairClass)>,
+    pub knowledge_parameter_parameter: Vec<(PreviewParameter, PreviewParameter, KnowledgeState)>,
+    pub knowledge_gc_gc: Vec<(PreviewGraphClass, PreviewGraphClass, KnowledgeState)>,
+    pub contradictions: Vec<Contradiction>,
     pub arc_lf_lf: Vec<(PreviewLogicFragment, PreviewLogicFragment, ImplicationRelation)>,
     pub arc_op_op: Vec<(PreviewOperation, PreviewOperation, ImplicationRelation)>,
+    pub arc_grrel_grrel: Vec<(PreviewGraphRelation, PreviewGraphRelation, ImplicationRelation)>,
     pub arc_graph_graph: Vec<(PreviewGraph, PreviewGraph, InclusionRelationUnderGraphRelation)>,
     pub arc_gc_gc: Vec<(PreviewGraphClass, PreviewGraphClass, InclusionRelationUnderGraphRelation)>,
     pub arc_graph_gc: Vec<(PreviewGraph, PreviewGraphClass, InclusionRelationUnderGraphRelation)>,
@@ -456,8 +513,10 @@ pub struct Data {
     pub arc_gcprop_parameter: Vec<(PreviewGraphClassProperty, PreviewParameter, ImplicationRelation)>,
     pub arc_parameter_gcprop: Vec<(PreviewParameter, PreviewGraphClassProperty, EquivalenceRelation)>,
     pub arc_problem_problem: Vec<(PreviewProblem, PreviewProblem, ImplicationRelation)>,
-    pub arc_problem_gcprop: Vec<(PreviewProblem, PreviewGraphClassProperty, ClassicalSolvability)>,
-    pub arc_problem_parameter: Vec<(PreviewProblem, PreviewParameter, ParameterizedSolvability)>,
+    pub arc_problem_gcprop: Vec<(PreviewProblem, PreviewGraphClassProperty, SourcedSolvability<ClassicalSolvability>)>,
+    pub arc_problem_parameter: Vec<(PreviewProblem, PreviewParameter, SourcedSolvability<ParameterizedSolvability>)>,
+    #[serde(skip)]
+    pub relation_index: OnceLock<RelationIndex>,
 }
 
 pub fn convert_to_id_map<D>(arr: Vec<D>) -> HashMap<D::PreviewId, D>
@@ -468,8 +527,39 @@ where
     arr.into_iter().map(|x| (x.previewid(), x)).collect()
 }
 
+/// Serializes maps keyed by ids as the list of their values as the values carry their ids
+/// and serde_json only takes strings as keys.
+mod id_map {
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::convert_to_id_map;
+    use crate::data::id::HasPreviewId;
+
+    pub fn serialize<S, D>(map: &HashMap<D::PreviewId, D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        D: HasPreviewId + Serialize,
+    {
+        serializer.collect_seq(map.values())
+    }
+
+    pub fn deserialize<'de, De, D>(deserializer: De) -> Result<HashMap<D::PreviewId, D>, De::Error>
+    where
+        De: Deserializer<'de>,
+        D: HasPreviewId + Deserialize<'de>,
+        D::PreviewId: Hash + Eq,
+    {
+        Vec::<D>::deserialize(deserializer).map(convert_to_id_map)
+    }
+}
+
+#[derive(Default)]
 pub struct DataFields {
     pub tags: Vec<Tag>,
+    pub problems: Vec<Problem>,
     pub providers: Vec<Provider>,
     pub parametric_parameters: Vec<ParametricParameter>,
     pub parametric_graph_class: Vec<ParametricGraphClass>,
@@ -481,9 +571,16 @@ pub struct DataFields {
     pub graph_classes: Vec<GraphClass>,
     pub sources: Vec<Source>,
     pub graph_class_properties: Vec<GraphClassProperty>,
-    pub arc_parameter_parameter: Vec<(PreviewParameter, PreviewParameter, Cpx)>,
+    pub arc_parameter_parameter: Vec<(PreviewParameter, PreviewParameter, SourcedCpxInfo)>,
+    pub partial_results: Vec<PartialResult>,
+    pub equivalent_parameters: Vec<Vec<PreviewParameter>>,
+    pub pair_classes: Vec<(PreviewParameter, PreviewParameter, PairClass)>,
+    pub knowledge_parameter_parameter: Vec<(PreviewParameter, PreviewParameter, KnowledgeState)>,
+    pub knowledge_gc_gc: Vec<(PreviewGraphClass, PreviewGraphClass, KnowledgeState)>,
+    pub contradictions: Vec<Contradiction>,
     pub arc_lf_lf: Vec<(PreviewLogicFragment, PreviewLogicFragment, ImplicationRelation)>,
     pub arc_op_op: Vec<(PreviewOperation, PreviewOperation, ImplicationRelation)>,
+    pub arc_grrel_grrel: Vec<(PreviewGraphRelation, PreviewGraphRelation, ImplicationRelation)>,
     pub arc_graph_graph: Vec<(PreviewGraph, PreviewGraph, InclusionRelationUnderGraphRelation)>,
     pub arc_gc_gc: Vec<(PreviewGraphClass, PreviewGraphClass, InclusionRelationUnderGraphRelation)>,
     pub arc_graph_gc: Vec<(PreviewGraph, PreviewGraphClass, InclusionRelationUnderGraphRelation)>,
@@ -494,8 +591,8 @@ pub struct DataFields {
     pub arc_gcprop_parameter: Vec<(PreviewGraphClassProperty, PreviewParameter, ImplicationRelation)>,
     pub arc_parameter_gcprop: Vec<(PreviewParameter, PreviewGraphClassProperty, EquivalenceRelation)>,
     pub arc_problem_problem: Vec<(PreviewProblem, PreviewProblem, ImplicationRelation)>,
-    pub arc_problem_gcprop: Vec<(PreviewProblem, PreviewGraphClassProperty, ClassicalSolvability)>,
-    pub arc_problem_parameter: Vec<(PreviewProblem, PreviewParameter, ParameterizedSolvability)>,
+    pub arc_problem_gcprop: Vec<(PreviewProblem, PreviewGraphClassProperty, SourcedSolvability<ClassicalSolvability>)>,
+    pub arc_problem_parameter: Vec<(PreviewProblem, PreviewParameter, SourcedSolvability<ParameterizedSolvability>)>,
 }
 
 impl Data {
@@ -512,12 +609,20 @@ impl Data {
             parametric_parameters: convert_to_id_map(fields.parametric_parameters),
             providers: convert_to_id_map(fields.providers),
             tags: convert_to_id_map(fields.tags),
+            problems: convert_to_id_map(fields.problems),
             sorted_sources: fields.sources.iter().map(|x| x.previewid()).collect(),
             sources: convert_to_id_map(fields.sources),
             graph_class_properties: convert_to_id_map(fields.graph_class_properties),
             arc_parameter_parameter: fields.arc_parameter_parameter,
+            partial_results: fields.partial_results,
+            equivalent_parameters: fields.equivalent_parameters,
+            pair_classes: fields.pair_classes,
+            knowledge_parameter_parameter: fields.knowledge_parameter_parameter,
+            knowledge_gc_gc: fields.knowledge_gc_gc,
+            contradictions: fields.contradictions,
             arc_lf_lf: fields.arc_lf_lf,
             arc_op_op: fields.arc_op_op,
+            arc_grrel_grrel: fields.arc_grrel_grrel,
             arc_graph_graph: fields.arc_graph_graph,
             arc_gc_gc: fields.arc_gc_gc,
             arc_gc_par: fields.arc_gc_par,
@@ -530,6 +635,18 @@ impl Data {
             arc_problem_problem: fields.arc_problem_problem,
             arc_problem_gcprop: fields.arc_problem_gcprop,
             arc_problem_parameter: fields.arc_problem_parameter,
+            relation_index: OnceLock::new(),
         }
     }
+
+    /// Parameters which bound the same graphs as `parameter`, without `parameter` itself.
+    pub fn equivalent_parameters(&self, parameter: &PreviewParameterId) -> Vec<&PreviewParameter> {
+        self.equivalent_parameters
+            .iter()
+            .find(|class| class.iter().any(|x| &x.id == parameter))
+            .into_iter()
+            .flatten()
+            .filter(|x| &x.id != parameter)
+            .collect()
+    }
 }
@@ -2,10 +2,11 @@
 //! additional parsing and extra packages.
 
 use core::fmt;
+use std::sync::LazyLock;
 
-use biblatex::{Chunks, DateValue, Datetime, Entry, PermissiveType};
-use chrono::NaiveDate;
+use biblatex::{ChunksExt, DateValue, Datetime, Entry};
 use log::error;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
@@ -53,35 +54,139 @@ impl fmt::Debug for Date {
     }
 }
 
+/// Statuses of entries that were not published yet, they are not dates but are no errors either.
+const UNPUBLISHED: [&str; 5] = ["in press", "forthcoming", "to appear", "accepted", "submitted"];
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+impl From<Datetime> for Date {
+    fn from(datetime: Datetime) -> Date {
+        Date {
+            year: Some(datetime.year),
+            month: datetime.month.map(|m| m + 1),
+            day: datetime.day.map(|d| d + 1),
+        }
+    }
+}
+
+impl From<DateValue> for Date {
+    /// Open ranges are represented by their only endpoint and closed ones by their start.
+    fn from(value: DateValue) -> Date {
+        match value {
+            DateValue::At(datetime)
+            | DateValue::After(datetime)
+            | DateValue::Before(datetime)
+            | DateValue::Between(datetime, _) => Date::from(datetime),
+        }
+    }
+}
+
+static DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([0-9]{4})(?:-([0-9]{1,2})(?:-([0-9]{1,2}))?)?").unwrap());
+
+/// Parses dates of the form `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, possibly followed by other text.
+/// Returns `None` for unpublished works and an error for anything else.
+fn parse_date_str(value: &str) -> Result<Option<Date>, ()> {
+    let value = value.trim().to_lowercase();
+    if UNPUBLISHED.contains(&value.as_str()) {
+        return Ok(None);
+    }
+    let caps = DATE_RE.captures(&value).ok_or(())?;
+    let number = |i: usize| -> Result<Option<u8>, ()> {
+        caps.get(i).map(|x| x.as_str().parse().map_err(|_| ())).transpose()
+    };
+    let date = Date {
+        year: Some(caps[1].parse().map_err(|_| ())?),
+        month: number(2)?,
+        day: number(3)?,
+    };
+    let valid_month = date.month.is_none_or(|m| (1..=12).contains(&m));
+    let valid_day = date.day.is_none_or(|d| (1..=31).contains(&d));
+    if valid_month && valid_day {
+        Ok(Some(date))
+    } else {
+        Err(())
+    }
+}
+
+/// Parses a month given either by its number or by its (abbreviated) english name.
+fn parse_month(value: &str) -> Option<u8> {
+    let value = value.trim().to_lowercase();
+    if let Ok(month) = value.parse::<u8>() {
+        return (1..=12).contains(&month).then_some(month);
+    }
+    let abbreviation = value.get(..3)?;
+    MONTHS
+        .iter()
+        .position(|x| *x == abbreviation)
+        .map(|i| i as u8 + 1)
+}
+
+/// Date of an arXiv identifier, i.e., `YYMM.NNNNN` or the older `archive/YYMMNNN`.
+fn parse_eprint(value: &str) -> Option<Date> {
+    let number = value.trim().rsplit('/').next()?;
+    let year: i32 = number.get(..2)?.parse().ok()?;
+    let month: u8 = number.get(2..4)?.parse().ok()?;
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+    Some(Date {
+        year: Some(if year < 91 { 2000 + year } else { 1900 + year }),
+        month: Some(month),
+        day: None,
+    })
+}
+
 impl From<&Entry> for Date {
+    /// Reads the `date` field, falling back on the `year`, `month`, and `day` fields and then
+    /// on the `eprint` field. Values that cannot be understood are reported and the date is
+    /// left unknown.
     fn from(entry: &Entry) -> Date {
-        match entry.date() {
-            Ok(permissive_date) => {
-                match permissive_date {
-                    PermissiveType::Chunks(chunks) => {
-                        error!("unimplemented {:?}", chunks);
-                    }
-                    PermissiveType::Typed(date) => match date.value {
-                        DateValue::At(datetime) => {
-                            let year = Some(datetime.year);
-                            let mut month = datetime.month;
-                            if let Some(m) = month {
-                                month = Some(m + 1);
-                            }
-                            let mut day = datetime.day;
-                            if let Some(d) = day {
-                                day = Some(d + 1);
-                            }
-                            return Date { year, month, day };
+        let field = |key: &str| entry.get(key).map(|x| x.format_verbatim());
+        if let Some(chunks) = entry.get("date") {
+            if let Ok(date) = chunks.parse::<biblatex::Date>() {
+                return Date::from(date.value);
+            }
+            let value = chunks.format_verbatim();
+            match parse_date_str(&value) {
+                Ok(Some(date)) => return date,
+                Ok(None) => {}
+                Err(()) => error!("{}: cannot parse date `{}`", entry.key, value),
+            }
+        }
+        if let Some(value) = field("year") {
+            match parse_date_str(&value) {
+                Ok(Some(mut date)) => {
+                    if let Some(month) = field("month") {
+                        date.month = parse_month(&month);
+                        if date.month.is_none() {
+                            error!("{}: cannot parse month `{}`", entry.key, month);
                         }
-                        _ => {
-                            error!("unimplemented date format");
+                    }
+                    if let (Some(_), Some(day)) = (date.month, field("day")) {
+                        date.day = day.trim().parse().ok().filter(|d| (1..=31).contains(d));
+                        if date.day.is_none() {
+                            error!("{}: cannot parse day `{}`", entry.key, day);
                         }
-                    },
+                    }
+                    return date;
                 }
-                Date::empty()
+                Ok(None) => {}
+                Err(()) => error!("{}: cannot parse year `{}`", entry.key, value),
             }
-            Err(_) => Date::empty(),
         }
+        if let Some(value) = field("eprint") {
+            match parse_eprint(&value) {
+                Some(date) => return date,
+                None => error!("{}: cannot parse date of eprint `{}`", entry.key, value),
+            }
+        }
+        Date::empty()
     }
 }
+
+#[cfg(test)]
+#[path = "tests/date.rs"]
+mod tests;
@@ -8,8 +8,10 @@ use serde::{Deserialize, Serialize};
 use crate::data::data::Named;
 use crate::data::id::*;
 use crate::data::link::Link;
-use crate::data::preview::PreviewSource;
+use crate::data::preview::{PreviewParameter, PreviewSource};
+use crate::work::inference::PartialResult;
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Value {
     Value(u32),
     Infinity,
@@ -116,34 +118,85 @@ pub enum SourcedBound {
 pub enum CreatedBy {
     TransitiveInclusion(usize, usize),
     TransitiveExclusion(usize, usize),
+    TransitiveLowerBound(usize, usize),
     ParallelComposition(usize, usize),
     SameThroughEquivalence(usize, usize),
     SumInclusion(Vec<usize>),
     TransferredFrom(TransferGroup, usize),
     Directly(PreviewSource),
+    /// Bound between distances to graph classes given by the inclusion of the classes
+    /// that the source states in the fact.
+    ClassInclusion(PreviewSource, PreviewShowedId),
+    ByDefinition(PreviewParameter),
     Todo,
 }
 
-/// /// A processed variant of CpxInfo which has links to sources that lead to a given result
-/// #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)] // todo
-/// pub enum SourcedCpxInfo {
-///     Equal {
-///         source: PartialResult,
-///     },
-///     Inclusion {
-///         mn: Option<(CpxTime, PartialResult)>,
-///         mx: Option<(CpxTime, PartialResult)>,
-///     },
-///     Exclusion {
-///         source: PartialResult,
-///     },
-///     Unknown,
-/// }
+/// A processed variant of CpxInfo which has links to sources that lead to a given result
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum SourcedCpxInfo {
+    Equal {
+        source: PartialResult,
+    },
+    Inclusion {
+        mn: Option<(CpxTime, PartialResult)>,
+        mx: Option<(CpxTime, PartialResult)>,
+    },
+    Exclusion {
+        source: PartialResult,
+    },
+    Unknown,
+}
+
+/// How two parameters compare when both directions are known, seen from the first one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum PairClass {
+    /// Both parameters upper bound each other.
+    Equivalent,
+    /// The first parameter upper bounds the second one but not the other way around.
+    StrictlyBounds,
+    /// The second parameter upper bounds the first one but not the other way around.
+    StrictlyBoundedBy,
+    /// Neither parameter upper bounds the other one.
+    Incomparable,
+}
+
+impl PairClass {
+    /// The same classification seen from the second parameter.
+    pub fn flip(&self) -> Self {
+        match self {
+            Self::StrictlyBounds => Self::StrictlyBoundedBy,
+            Self::StrictlyBoundedBy => Self::StrictlyBounds,
+            other => *other,
+        }
+    }
+}
+
+/// Open question between the best known lower and upper bound of a relation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BoundGap {
+    pub lower: CpxTime,
+    pub upper: CpxTime,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DrawingKind {
+    /// Hasse diagram of the bounds between the parameters.
+    Hasse,
+    /// Table comparing every pair of the parameters.
+    Table,
+}
 
+/// Figure of a source that is redrawn from the current results of HOPS.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-pub enum Drawing {
-    Table(Vec<Link>),
-    Hasse(Vec<Link>),
+pub struct Drawing {
+    pub id: PreviewDrawingId,
+    pub page: Page,
+    pub kind: DrawingKind,
+    pub parameters: Vec<PreviewParameter>,
+    /// Whether the first parameter upper bounds the second one as known from the sources up to
+    /// the date of the figure, pairs that were not resolved by then are missing.
+    pub known_then: Vec<(PreviewParameterId, PreviewParameterId, bool)>,
 }
 
 impl CpxInfo {
@@ -157,37 +210,67 @@ impl CpxInfo {
         }
     }
 
-    // pub fn into_sourced(self, partial_result: PartialResult) -> SourcedCpxInfo {
-    //     match self.clone() {
-    //         CpxInfo::Equal => SourcedCpxInfo::Equal {
-    //             source: partial_result,
-    //         },
-    //         CpxInfo::Inclusion { mn, mx } => SourcedCpxInfo::Inclusion {
-    //             mn: mn.map(|x| (x, partial_result.clone())),
-    //             mx: mx.map(|x| (x, partial_result.clone())),
-    //         },
-    //         CpxInfo::Exclusion => SourcedCpxInfo::Exclusion {
-    //             source: partial_result,
-    //         },
-    //         CpxInfo::Unknown => SourcedCpxInfo::Unknown,
-    //     }
-    // }
-}
-
-// impl From<SourcedCpxInfo> for CpxInfo {
-//     fn from(sourced: SourcedCpxInfo) -> CpxInfo {
-//         match sourced {
-//             SourcedCpxInfo::Equal { source } => CpxInfo::Equal,
-//             SourcedCpxInfo::Inclusion { mn, mx } => CpxInfo::Inclusion {
-//                 mn: mn.map(|(x, _)| x),
-//                 mx: mx.map(|(x, _)| x),
-//             },
-//             SourcedCpxInfo::Exclusion { source: _ } => CpxInfo::Exclusion,
-//             SourcedCpxInfo::Unknown => CpxInfo::Unknown,
-//         }
-//     }
-// }
-//
+    /// Whether the relation is known to be an upper bound (`Some(true)`) or known
+    /// not to be one (`Some(false)`).
+    pub fn is_bounded(&self) -> Option<bool> {
+        match self {
+            CpxInfo::Equal | CpxInfo::Inclusion { mx: Some(_), .. } => Some(true),
+            CpxInfo::Exclusion => Some(false),
+            CpxInfo::Inclusion { mx: None, .. } | CpxInfo::Unknown => None,
+        }
+    }
+
+    /// Whether the known lower and upper bound of the relation meet.
+    pub fn is_tight(&self) -> bool {
+        matches!(self, CpxInfo::Inclusion { mn: Some(a), mx: Some(b) } if a == b)
+    }
+
+    /// Known lower and upper bound of a relation that do not meet yet. Relations without
+    /// a lower bound are left out as the lower bound was never looked for in most of them.
+    pub fn gap(&self) -> Option<BoundGap> {
+        match self {
+            CpxInfo::Inclusion {
+                mn: Some(mn),
+                mx: Some(mx),
+            } if mn.is_smaller_than(mx) => Some(BoundGap {
+                lower: mn.clone(),
+                upper: mx.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn into_sourced(self, partial_result: PartialResult) -> SourcedCpxInfo {
+        match self {
+            CpxInfo::Equal => SourcedCpxInfo::Equal {
+                source: partial_result,
+            },
+            CpxInfo::Inclusion { mn, mx } => SourcedCpxInfo::Inclusion {
+                mn: mn.map(|x| (x, partial_result.clone())),
+                mx: mx.map(|x| (x, partial_result.clone())),
+            },
+            CpxInfo::Exclusion => SourcedCpxInfo::Exclusion {
+                source: partial_result,
+            },
+            CpxInfo::Unknown => SourcedCpxInfo::Unknown,
+        }
+    }
+}
+
+impl From<SourcedCpxInfo> for CpxInfo {
+    fn from(sourced: SourcedCpxInfo) -> CpxInfo {
+        match sourced {
+            SourcedCpxInfo::Equal { source: _ } => CpxInfo::Equal,
+            SourcedCpxInfo::Inclusion { mn, mx } => CpxInfo::Inclusion {
+                mn: mn.map(|(x, _)| x),
+                mx: mx.map(|(x, _)| x),
+            },
+            SourcedCpxInfo::Exclusion { source: _ } => CpxInfo::Exclusion,
+            SourcedCpxInfo::Unknown => CpxInfo::Unknown,
+        }
+    }
+}
+
 pub enum ComparisonResult {
     Better,
     Worse,
@@ -205,3 +288,7 @@ impl ComparisonResult {
         }
     }
 }
+
+#[cfg(test)]
+#[path = "tests/enums.rs"]
+mod tests;
@@ -124,6 +124,7 @@ macro_rules! define_type {
     };
 }
 
+define_type!(TypeDrawing, DrawingId, PreviewDrawingId);
 define_type!(TypeGraph, GraphId, PreviewGraphId);
 define_type!(TypeGraphClass, GraphClassId, PreviewGraphClassId);
 define_type!(TypeOperation, OperationId, PreviewOperationId);
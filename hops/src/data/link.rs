@@ -5,7 +5,8 @@ use crate::data::{
     enums::SourceKey,
     id::{HasId, PreviewId},
     preview::{
-        PreviewGraphClass, PreviewParameter, PreviewParametricGraphClass, PreviewSource, PreviewTag,
+        PreviewGraphClass, PreviewGraphClassProperty, PreviewGraphRelation, PreviewLogicFragment, PreviewParameter,
+        PreviewParametricGraphClass, PreviewProblem, PreviewSource, PreviewTag,
     },
 };
 
@@ -60,6 +61,33 @@ impl Linkable for PreviewTag {
     }
 }
 
+impl Linkable for PreviewGraphRelation {
+    fn get_link(&self) -> Link {
+        Link {
+            url: html_base(&self.id.to_string()),
+            name: self.name.name.clone(),
+        }
+    }
+}
+
+impl Linkable for PreviewLogicFragment {
+    fn get_link(&self) -> Link {
+        Link {
+            url: html_base(&self.id.to_string()),
+            name: self.name.name.clone(),
+        }
+    }
+}
+
+impl Linkable for PreviewProblem {
+    fn get_link(&self) -> Link {
+        Link {
+            url: html_base(&self.id.to_string()),
+            name: self.name.name.clone(),
+        }
+    }
+}
+
 impl Linkable for PreviewGraphClass {
     fn get_link(&self) -> Link {
         Link {
@@ -78,6 +106,15 @@ impl Linkable for PreviewParametricGraphClass {
     }
 }
 
+impl Linkable for PreviewGraphClassProperty {
+    fn get_link(&self) -> Link {
+        Link {
+            url: html_base(&self.id.to_string()),
+            name: self.name_core.name.clone(),
+        }
+    }
+}
+
 impl Linkable for PreviewParameter {
     fn get_link(&self) -> Link {
         Link {
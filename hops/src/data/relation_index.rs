@@ -0,0 +1,193 @@
+//! Constant time lookup of the relations stored in `Data`.
+//!
+//! The relations are kept as lists of arcs, the index remembers at which positions
+//! the arcs between each ordered pair are stored.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::data::bound::Bound;
+use crate::data::data::{Data, KnowledgeState};
+use crate::data::enums::{CpxInfo, CreatedBy, PairClass, SourcedCpxInfo};
+use crate::data::id::*;
+use crate::input::source::{ImplicationRelation, InclusionRelationUnderGraphRelation};
+use crate::work::inference::{PartialResult, PartialResultsBuilder};
+
+type Positions<Id> = HashMap<(Id, Id), Vec<usize>>;
+
+fn positions<'a, Id>(pairs: impl Iterator<Item = (&'a Id, &'a Id)>) -> Positions<Id>
+where
+    Id: 'a + Hash + Eq + Clone,
+{
+    let mut res: Positions<Id> = HashMap::new();
+    for (i, (a, b)) in pairs.enumerate() {
+        res.entry((a.clone(), b.clone())).or_default().push(i);
+    }
+    res
+}
+
+#[derive(Debug, Default)]
+pub struct RelationIndex {
+    parameters: Positions<PreviewParameterId>,
+    graph_classes: Positions<PreviewGraphClassId>,
+    graph_class_properties: Positions<PreviewGraphClassPropertyId>,
+    pair_classes: Positions<PreviewParameterId>,
+    parameter_knowledge: Positions<PreviewParameterId>,
+    graph_class_knowledge: Positions<PreviewGraphClassId>,
+}
+
+impl RelationIndex {
+    pub fn new(data: &Data) -> Self {
+        Self {
+            parameters: positions(data.arc_parameter_parameter.iter().map(|(a, b, _)| (&a.id, &b.id))),
+            graph_classes: positions(data.arc_gc_gc.iter().map(|(a, b, _)| (&a.id, &b.id))),
+            graph_class_properties: positions(data.arc_gcprop_gcprop.iter().map(|(a, b, _)| (&a.id, &b.id))),
+            pair_classes: positions(data.pair_classes.iter().map(|(a, b, _)| (&a.id, &b.id))),
+            parameter_knowledge: positions(data.knowledge_parameter_parameter.iter().map(|(a, b, _)| (&a.id, &b.id))),
+            graph_class_knowledge: positions(data.knowledge_gc_gc.iter().map(|(a, b, _)| (&a.id, &b.id))),
+        }
+    }
+}
+
+/// Entities whose relations are indexed, `Arc` is what is stored for an ordered pair.
+pub trait Indexed: Sized {
+    type Arc;
+    fn positions(index: &RelationIndex) -> &Positions<Self>;
+    fn arc(data: &Data, position: usize) -> &Self::Arc;
+}
+
+impl Indexed for PreviewParameterId {
+    type Arc = SourcedCpxInfo;
+    fn positions(index: &RelationIndex) -> &Positions<Self> {
+        &index.parameters
+    }
+    fn arc(data: &Data, position: usize) -> &Self::Arc {
+        &data.arc_parameter_parameter[position].2
+    }
+}
+
+impl Indexed for PreviewGraphClassId {
+    type Arc = InclusionRelationUnderGraphRelation;
+    fn positions(index: &RelationIndex) -> &Positions<Self> {
+        &index.graph_classes
+    }
+    fn arc(data: &Data, position: usize) -> &Self::Arc {
+        &data.arc_gc_gc[position].2
+    }
+}
+
+impl Indexed for PreviewGraphClassPropertyId {
+    type Arc = ImplicationRelation;
+    fn positions(index: &RelationIndex) -> &Positions<Self> {
+        &index.graph_class_properties
+    }
+    fn arc(data: &Data, position: usize) -> &Self::Arc {
+        &data.arc_gcprop_gcprop[position].2
+    }
+}
+
+/// Everything stored about a pair, `forward` goes from the first to the second entity.
+#[derive(Debug)]
+pub struct PairRelation<'a, T> {
+    pub forward: Vec<&'a T>,
+    pub backward: Vec<&'a T>,
+}
+
+impl PairRelation<'_, SourcedCpxInfo> {
+    /// Combined relation from the first parameter to the second one.
+    pub fn forward_cpx(&self) -> CpxInfo {
+        Self::combined(&self.forward).map_or(CpxInfo::Unknown, |x| x.cpx)
+    }
+
+    /// Combined relation from the second parameter to the first one.
+    pub fn backward_cpx(&self) -> CpxInfo {
+        Self::combined(&self.backward).map_or(CpxInfo::Unknown, |x| x.cpx)
+    }
+
+    /// Exact upper bound from the first parameter to the second one, if known.
+    pub fn forward_bound(&self) -> Option<Bound> {
+        Self::combined(&self.forward)?.bound
+    }
+
+    /// Exact upper bound from the second parameter to the first one, if known.
+    pub fn backward_bound(&self) -> Option<Bound> {
+        Self::combined(&self.backward)?.bound
+    }
+
+    /// Combines the arcs in parallel, an arc that contradicts the ones before it is skipped.
+    fn combined(arcs: &[&SourcedCpxInfo]) -> Option<PartialResult> {
+        let mut builder = PartialResultsBuilder::new();
+        let mut res: Option<PartialResult> = None;
+        for arc in arcs {
+            let (relation, bound) = match arc {
+                SourcedCpxInfo::Unknown => continue,
+                SourcedCpxInfo::Inclusion {
+                    mx: Some((_, source)), ..
+                } => (source.relation.clone(), source.bound.clone()),
+                SourcedCpxInfo::Inclusion {
+                    mn: Some((_, source)), ..
+                }
+                | SourcedCpxInfo::Equal { source }
+                | SourcedCpxInfo::Exclusion { source } => (source.relation.clone(), None),
+                SourcedCpxInfo::Inclusion { mn: None, mx: None } => continue,
+            };
+            let next = builder.bounded_partial_result(CreatedBy::Todo, CpxInfo::from((*arc).clone()), relation, bound);
+            res = Some(match res {
+                None => next,
+                Some(current) => match current.combine_parallel(&next, &mut builder) {
+                    Ok(Some(better)) => better,
+                    Ok(None) | Err(_) => current,
+                },
+            });
+        }
+        res
+    }
+}
+
+impl Data {
+    /// Relations between `a` and `b` in both directions, each with the partial results it came from.
+    pub fn relation<Id: Indexed + Hash + Eq + Clone>(&self, a: &Id, b: &Id) -> PairRelation<'_, Id::Arc> {
+        let index = self.relation_index.get_or_init(|| RelationIndex::new(self));
+        let arcs = |x: &Id, y: &Id| -> Vec<&Id::Arc> {
+            Id::positions(index)
+                .get(&(x.clone(), y.clone()))
+                .into_iter()
+                .flatten()
+                .map(|&position| Id::arc(self, position))
+                .collect()
+        };
+        PairRelation {
+            forward: arcs(a, b),
+            backward: arcs(b, a),
+        }
+    }
+
+    /// Classification of the pair of parameters seen from `a`, if both directions are known.
+    pub fn pair_class(&self, a: &PreviewParameterId, b: &PreviewParameterId) -> Option<PairClass> {
+        let index = self.relation_index.get_or_init(|| RelationIndex::new(self));
+        let &position = index.pair_classes.get(&(a.clone(), b.clone()))?.first()?;
+        Some(self.pair_classes[position].2)
+    }
+
+    /// How well established the relation from `a` to `b` is.
+    pub fn parameter_knowledge(&self, a: &PreviewParameterId, b: &PreviewParameterId) -> KnowledgeState {
+        let index = self.relation_index.get_or_init(|| RelationIndex::new(self));
+        match index.parameter_knowledge.get(&(a.clone(), b.clone())).and_then(|x| x.first()) {
+            Some(&position) => self.knowledge_parameter_parameter[position].2,
+            None => KnowledgeState::UnknownToHOPS,
+        }
+    }
+
+    /// How well established the relation from `a` to `b` is.
+    pub fn graph_class_knowledge(&self, a: &PreviewGraphClassId, b: &PreviewGraphClassId) -> KnowledgeState {
+        let index = self.relation_index.get_or_init(|| RelationIndex::new(self));
+        match index.graph_class_knowledge.get(&(a.clone(), b.clone())).and_then(|x| x.first()) {
+            Some(&position) => self.knowledge_gc_gc[position].2,
+            None => KnowledgeState::UnknownToHOPS,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/relation_index.rs"]
+mod tests;
@@ -73,6 +73,23 @@ where
         res
     }
 
+    /// Classes of mutually included sets which have at least two members.
+    pub fn get_eqclasses(&self) -> Vec<Vec<T>> {
+        let mut seen: HashSet<T> = HashSet::new();
+        let mut res = vec![];
+        for (a, _) in &self.first_subset_of_second {
+            if seen.contains(a) {
+                continue;
+            }
+            let class = self.get_eqsets(a);
+            seen.extend(class.iter().cloned());
+            if class.len() > 1 {
+                res.push(class);
+            }
+        }
+        res
+    }
+
     pub fn get_subsets(&self, a: &T) -> Vec<T> {
         let seta: HashSet<T> = HashSet::from_iter(self.get_all_subsets(a));
         let setb: HashSet<T> = HashSet::from_iter(self.get_eqsets(a));
@@ -0,0 +1,43 @@
+use super::*;
+
+fn matrix(size: usize, pairs: &[(usize, usize)]) -> BitMatrix {
+    let mut res = BitMatrix::new(size);
+    for &(a, b) in pairs {
+        res.set(a, b);
+    }
+    res
+}
+
+#[test]
+fn product_composes_pairs() {
+    let a = matrix(70, &[(0, 1), (1, 65)]);
+    let res = a.product(&a);
+    assert_eq!(res, matrix(70, &[(0, 65)]));
+}
+
+#[test]
+fn transpose_swaps_pairs() {
+    let a = matrix(3, &[(0, 2), (1, 1)]);
+    assert_eq!(a.transpose(), matrix(3, &[(2, 0), (1, 1)]));
+}
+
+#[test]
+fn ones_lists_columns_across_words() {
+    let a = matrix(130, &[(4, 3), (4, 64), (4, 129)]);
+    assert_eq!(a.ones(4).collect::<Vec<_>>(), vec![3, 64, 129]);
+    assert!(a.ones(5).next().is_none());
+}
+
+#[test]
+fn first_common_column() {
+    let (a, b) = (matrix(100, &[(0, 70), (0, 80)]), matrix(100, &[(0, 80), (0, 90)]));
+    assert_eq!(BitMatrix::first_common(a.row(0), b.row(0)), Some(80));
+    assert_eq!(BitMatrix::first_common(a.row(0), b.row(1)), None);
+}
+
+#[test]
+fn difference_without_diagonal() {
+    let a = matrix(3, &[(0, 0), (0, 1), (1, 2)]);
+    let res = a.difference(&matrix(3, &[(1, 2)])).without_diagonal();
+    assert_eq!(res, matrix(3, &[(0, 1)]));
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+fn bound(s: &str) -> Bound {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parses_and_prints() {
+    assert_eq!(bound("3·2^{k-1}").to_string(), "3*2^(k - 1)");
+    assert_eq!(bound("3 \\cdot 2^{k-1}").to_latex(), "3 \\cdot 2^{k - 1}");
+    assert_eq!(bound("k^2").to_latex(), "k^{2}");
+    assert_eq!(bound("O(k log k)").to_latex(), "O(k \\log k)");
+    assert_eq!(bound("2^{O(k^2)}").to_string(), "2^O(k^2)");
+    assert!("k^k".parse::<Bound>().is_err());
+    assert!("x + 1".parse::<Bound>().is_err());
+}
+
+#[test]
+fn classes() {
+    assert_eq!(bound("7").time(), CpxTime::Constant);
+    assert_eq!(bound("2k+1").time(), CpxTime::Linear);
+    assert_eq!(bound("O(k log k)").time(), CpxTime::Polynomial);
+    assert_eq!(bound("3·2^{k-1}").time(), CpxTime::Exponential);
+    assert_eq!(bound("2^{O(k^2)}").time(), CpxTime::Tower);
+    assert_eq!(bound("2^{2^k}").time(), CpxTime::Tower);
+}
+
+#[test]
+fn composition_substitutes() {
+    let composed = bound("k^2").compose(&bound("2k+1"));
+    assert_eq!(composed.to_string(), "(2*k + 1)^2");
+    assert_eq!(composed.time(), CpxTime::Polynomial);
+    assert_eq!(bound("2^k").compose(&bound("O(k^2)")).to_latex(), "2^{O(k^{2})}");
+    assert_eq!(bound("k+1").compose(&bound("k-1")), Bound::Parameter);
+}
+
+#[test]
+fn comparison_ignores_constant_factors() {
+    assert_eq!(bound("2^k").compare(&bound("2^{2k}")), Some(Ordering::Equal));
+    assert_eq!(bound("k^2").compare(&bound("k log k")), Some(Ordering::Greater));
+    assert_eq!(bound("2^{k}").compare(&bound("k^10")), Some(Ordering::Greater));
+    assert_eq!(bound("2^{log k}").compare(&bound("k^2")), None);
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+use biblatex::Bibliography;
+
+fn date(fields: &str) -> String {
+    let bibliography = Bibliography::parse(&format!("@article{{key, {}}}", fields)).unwrap();
+    Date::from(bibliography.get("key").unwrap()).to_string()
+}
+
+#[test]
+fn typed_dates_and_ranges() {
+    assert_eq!(date("date = {2021-03-04}"), "2021/03/04");
+    assert_eq!(date("date = {2021/2022}"), "2021");
+    assert_eq!(date("date = {../2019-05}"), "2019/05");
+}
+
+#[test]
+fn year_and_month_fields() {
+    assert_eq!(date("year = {2020}"), "2020");
+    assert_eq!(date("year = {2020}, month = {7}"), "2020/07");
+    assert_eq!(date("year = {2020}, month = sep, day = {9}"), "2020/09/09");
+}
+
+#[test]
+fn chunks_and_eprints() {
+    assert_eq!(date("date = {2023-05 (online)}"), "2023/05");
+    assert_eq!(date("date = {in press}, year = {2024}"), "2024");
+    assert_eq!(date("year = {forthcoming}"), "unknown");
+    assert_eq!(date("eprint = {2301.01234}"), "2023/01");
+    assert_eq!(date("eprint = {math/0601001}"), "2006/01");
+}
+
+#[test]
+fn unparseable_dates_are_unknown() {
+    assert_eq!(date("date = {sometime}"), "unknown");
+    assert_eq!(date("year = {2020}, month = {smarch}"), "2020");
+    assert_eq!(date(""), "unknown");
+    assert_eq!(date("date = {２０２３}"), "unknown");
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn upper_bound_without_lower_bound_is_no_gap() {
+    let upper = CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(CpxTime::Exponential),
+    };
+    assert_eq!(upper.gap(), None);
+}
+
+#[test]
+fn gap_between_known_bounds() {
+    let bounds = |mn, mx| CpxInfo::Inclusion {
+        mn: Some(mn),
+        mx: Some(mx),
+    };
+    assert_eq!(
+        bounds(CpxTime::Linear, CpxTime::Exponential).gap(),
+        Some(BoundGap {
+            lower: CpxTime::Linear,
+            upper: CpxTime::Exponential,
+        })
+    );
+    assert_eq!(bounds(CpxTime::Exponential, CpxTime::Exponential).gap(), None);
+}
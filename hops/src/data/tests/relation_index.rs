@@ -0,0 +1,101 @@
+use super::*;
+use crate::data::data::{DataFields, NameCore};
+use crate::data::enums::{CpxTime, CreatedBy};
+use crate::data::preview::PreviewParameter;
+use crate::work::inference::{PartialResult, WorkRelation};
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 1,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn partial_result(handle: usize, cpx: CpxInfo) -> PartialResult {
+    PartialResult {
+        handle,
+        created_by: CreatedBy::Todo,
+        relation: WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b")),
+        cpx,
+        bound: None,
+    }
+}
+
+fn make_data() -> Data {
+    let upper = CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(CpxTime::Linear),
+    };
+    Data::new(DataFields {
+        arc_parameter_parameter: vec![
+            (
+                parameter("a"),
+                parameter("b"),
+                SourcedCpxInfo::Inclusion {
+                    mn: None,
+                    mx: Some((CpxTime::Linear, partial_result(0, upper))),
+                },
+            ),
+            (
+                parameter("b"),
+                parameter("a"),
+                SourcedCpxInfo::Exclusion {
+                    source: partial_result(1, CpxInfo::Exclusion),
+                },
+            ),
+        ],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn relation_in_both_directions() {
+    let data = make_data();
+    let (a, b) = (PreviewParameterId::from("a"), PreviewParameterId::from("b"));
+    let relation = data.relation(&a, &b);
+    assert_eq!(
+        relation.forward_cpx(),
+        CpxInfo::Inclusion {
+            mn: None,
+            mx: Some(CpxTime::Linear)
+        }
+    );
+    assert_eq!(relation.backward_cpx(), CpxInfo::Exclusion);
+    assert_eq!(data.relation(&b, &a).forward_cpx(), CpxInfo::Exclusion);
+}
+
+#[test]
+fn missing_pair_is_unknown() {
+    let data = make_data();
+    let relation = data.relation(&PreviewParameterId::from("a"), &PreviewParameterId::from("c"));
+    assert!(relation.forward.is_empty());
+    assert_eq!(relation.backward_cpx(), CpxInfo::Unknown);
+}
+
+#[test]
+fn parallel_arcs_are_combined() {
+    let lower = CpxInfo::Inclusion {
+        mn: Some(CpxTime::Linear),
+        mx: None,
+    };
+    let upper = CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(CpxTime::Polynomial),
+    };
+    let data = Data::new(DataFields {
+        arc_parameter_parameter: vec![
+            (parameter("a"), parameter("b"), lower.clone().into_sourced(partial_result(0, lower))),
+            (parameter("a"), parameter("b"), upper.clone().into_sourced(partial_result(1, upper))),
+        ],
+        ..Default::default()
+    });
+    let relation = data.relation(&PreviewParameterId::from("a"), &PreviewParameterId::from("b"));
+    assert_eq!(
+        relation.forward_cpx(),
+        CpxInfo::Inclusion {
+            mn: Some(CpxTime::Linear),
+            mx: Some(CpxTime::Polynomial)
+        }
+    );
+}
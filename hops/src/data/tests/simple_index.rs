@@ -18,6 +18,14 @@ fn eqsets_mutual_inclusion() {
     assert_eq!(eq, vec!["A", "B"]);
 }
 
+#[test]
+fn eqclasses_skip_singletons() {
+    let idx = make_index();
+    let mut classes = idx.get_eqclasses();
+    classes.iter_mut().for_each(|class| class.sort());
+    assert_eq!(classes, vec![vec!["A", "B"]]);
+}
+
 #[test]
 fn subsets_excludes_equivalences() {
     let idx = make_index();
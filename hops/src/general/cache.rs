@@ -1,33 +1,57 @@
 //! Save objects into temporary files to cache results of processing.
 
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::PathBuf;
 use std::{marker::PhantomData, path::Path};
 
 use anyhow::Result;
-use log::{debug, error};
-use serde::{Serialize, de::DeserializeOwned};
+use log::{debug, error, info};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
 use crate::general::file;
 
 pub struct Cache<T> {
     file: PathBuf,
+    inputs: DefaultHasher,
     _marker: PhantomData<T>,
 }
 
+/// Cached object along with the hash of the inputs it was computed from.
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    inputs: u64,
+    content: T,
+}
+
 /// Serializable object
 impl<T> Cache<T> {
     pub fn new(file: &Path) -> Self {
         Self {
             file: file.to_path_buf(),
+            inputs: DefaultHasher::new(),
             _marker: PhantomData,
         }
     }
 
+    /// Makes the cached object valid only for the same serialized `input`.
+    pub fn depends_on<I: Serialize>(mut self, input: &I) -> Self {
+        serde_json::to_string(input).unwrap_or_default().hash(&mut self.inputs);
+        self
+    }
+
+    fn inputs(&self) -> u64 {
+        self.inputs.finish()
+    }
+
     pub fn save(&self, object: &T) -> Result<()>
     where
         T: Serialize,
     {
-        let serialized = serde_json::to_string_pretty(object)?;
+        let entry = Entry {
+            inputs: self.inputs(),
+            content: object,
+        };
+        let serialized = serde_json::to_string_pretty(&entry)?;
         debug!("writing content");
         file::write_file_content(&self.file, serialized.as_str())?;
         debug!("done content");
@@ -48,9 +72,21 @@ impl<T> Cache<T> {
                 return None;
             }
         };
-        serde_json::from_str(&serialized).unwrap_or_else(|err| {
-            error!("{:?}", err);
-            None
-        })
+        let entry: Entry<T> = match serde_json::from_str(&serialized) {
+            Ok(res) => res,
+            Err(err) => {
+                error!("{:?}", err);
+                return None;
+            }
+        };
+        if entry.inputs != self.inputs() {
+            info!("inputs of {:?} changed, ignoring it", self.file);
+            return None;
+        }
+        Some(entry.content)
     }
 }
+
+#[cfg(test)]
+#[path = "tests/cache.rs"]
+mod tests;
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use super::*;
+
+/// Directory of a single test that is removed once the test ends.
+struct TestDir(PathBuf);
+
+impl TestDir {
+    fn new(test: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("hops_cache_{}_{}", std::process::id(), test));
+        let _ = std::fs::remove_dir_all(&dir);
+        Self(dir)
+    }
+
+    fn cache(&self, input: &str) -> Cache<Vec<u32>> {
+        Cache::new(&self.0.join("cache.json")).depends_on(&input)
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn same_inputs_load_saved_object() {
+    let dir = TestDir::new("same");
+    assert_eq!(dir.cache("a").load(), None);
+    dir.cache("a").save(&vec![1, 2]).unwrap();
+    assert_eq!(dir.cache("a").load(), Some(vec![1, 2]));
+}
+
+#[test]
+fn changed_inputs_reject_saved_object() {
+    let dir = TestDir::new("changed");
+    dir.cache("a").save(&vec![1, 2]).unwrap();
+    assert_eq!(dir.cache("b").load(), None);
+}
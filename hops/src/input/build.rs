@@ -37,6 +37,8 @@ pub struct CollectionBuilder {
 
 pub static UNKNOWN_SOURCE_ID: &str = "myit4D";
 pub static ASSUMED_SOURCE_ID: &str = "9kg0oo";
+/// Graph relation under which plain inclusions of graph classes are stated.
+pub static SUBGRAPH_RELATION_ID: &str = "yVyItw";
 
 /// Defines a new graph class. We do not aim to have all graph
 /// classes in the database but only those that are very relevant
@@ -21,7 +21,7 @@ impl Concretizable for PreviewParametricParameterId {
             id: ParameterId::new(id),
             score,
             name_core: NameCore::new(name),
-            definition: RawParameterDefinition::FromParametricParameter(a.clone()),
+            definition: RawParameterDefinition::FromParametricParameter(a.clone(), value),
             tags: Vec::new(),
         })
     }
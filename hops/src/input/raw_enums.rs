@@ -1,12 +1,15 @@
+use serde::Serialize;
+
+use crate::data::enums::Value;
 use crate::data::id::*;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub enum RawOwn {
     Has,
     Is,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawParameterDefinition {
     GraphText(String),
     GraphClassText(String),
@@ -16,41 +19,41 @@ pub enum RawParameterDefinition {
     IntersectionParameters(Vec<PreviewParameterId>),
     IntersectionParameterProperty(PreviewParameterId, PreviewGraphClassPropertyId),
     IntersectionParameterGraphClass(PreviewParameterId, PreviewGraphClassId),
-    FromParametricParameter(PreviewParametricParameterId),
+    FromParametricParameter(PreviewParametricParameterId, Value),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawOperationDefinition {
     GraphOperation(PreviewOperationId),
     GraphClassOperation(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawGraphClassPropertyDefinition {
     Text(String),
     FromGraphClass(PreviewGraphClassId),
     FromParameter(PreviewParameterId),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawProblemDefinition {
     Text(String),
     ModelChecking(PreviewLogicFragmentId),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawGraphRelationDefinition {
     Text(String),
     IsomorphicAfterOperations(Vec<PreviewOperationId>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawGraphClassRelationDefinition {
     Text(String),
     GraphRelation(PreviewGraphRelationId),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawGraphClassDefinition {
     Text(String),
     IntersectionGraphClasses(Vec<PreviewGraphClassId>),
@@ -59,13 +62,13 @@ pub enum RawGraphClassDefinition {
     Parameter(PreviewParameterId),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawGraphClassVariant {
     GraphClass,
     GraphProperty,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum RawParametricParameterDefinition {
     GraphClassParameter(String),
     GraphParameter(String),
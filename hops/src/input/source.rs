@@ -1,6 +1,7 @@
 use log::warn;
 use serde::{Deserialize, Serialize};
 
+use crate::data::bound::Bound;
 use crate::data::data::Named;
 use crate::data::enums::*;
 use crate::data::id::*;
@@ -8,7 +9,7 @@ use crate::data::link::Link;
 use crate::input::build::CollectionBuilder;
 use crate::input::raw::RawData;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum RawSourceKey {
     Bibtex { key: String },
     Online { url: String },
@@ -28,7 +29,7 @@ impl RawSourceKey {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RawShownRelation<F, T, D> {
     pub id: PreviewRelationId,
     pub subset: F,
@@ -36,20 +37,29 @@ pub struct RawShownRelation<F, T, D> {
     pub data: D, // CpxInfo
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct RawSource {
     pub id: SourceId,
     pub rawsourcekey: RawSourceKey,
     pub score: u32, // from 0 to 9
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct RawWrote {
     pub text: String,
     pub page: Page,
     pub facts: Vec<(ShowedId, RawWroteStatus, RawFact)>,
 }
 
+/// A figure of a source that compares the listed parameters.
+#[derive(Debug, Serialize)]
+pub struct RawDrawing {
+    pub id: DrawingId,
+    pub page: Page,
+    pub kind: DrawingKind,
+    pub parameters: Vec<PreviewParameterId>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EquivalenceRelation {
     Equivalent,
@@ -73,6 +83,7 @@ pub enum ClassicalSolvability {
 pub enum ParameterizedSolvability {
     Polynomial,
     Fpt,
+    Xp,
     Whard,
     ParaNpHard,
 }
@@ -82,6 +93,8 @@ pub enum ParameterizedSolvability {
 pub enum Cpx {
     Bounds(CpxTime, CpxTime),
     UpperBound(CpxTime),
+    /// Upper bound given by an exact function, see `bound`.
+    UpperBoundBy(Bound),
     LowerBound(CpxTime),
     StrictUpperBound(CpxTime),
     Exactly(CpxTime),
@@ -92,12 +105,29 @@ pub enum Cpx {
     Todo,
 }
 
+impl Cpx {
+    /// The exact upper bound if one was given.
+    pub fn bound(&self) -> Option<&Bound> {
+        match self {
+            Cpx::UpperBoundBy(bound) => Some(bound),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct InclusionRelationUnderGraphRelation {
     pub relation: ImplicationRelation,
     pub graph_relation: PreviewGraphRelationId,
 }
 
+/// Parses an exact bound such as `3*2^(k-1)`, the collection is not built with an invalid one.
+pub fn bound(expression: &str) -> Bound {
+    expression
+        .parse()
+        .unwrap_or_else(|err| panic!("invalid bound {:?}: {}", expression, err))
+}
+
 pub fn definition<T>(set: T) -> RawFact
 where
     T: Definable,
@@ -173,7 +203,7 @@ relatable!(PreviewProblemId, PreviewProblemId, ImplicationRelation, ProbProb);
 relatable!(PreviewProblemId, PreviewGraphClassPropertyId, ClassicalSolvability, ProbProp);
 relatable!(PreviewProblemId, PreviewParameterId, ParameterizedSolvability, ProbPar);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum Def {
     LogicFragment(PreviewLogicFragmentId),
     Parameter(PreviewParameterId),
@@ -186,7 +216,7 @@ pub enum Def {
     Property(PreviewGraphClassPropertyId),
 }
 
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize)]
 pub enum DefKind {
     LogicFragment,
     Parameter,
@@ -215,7 +245,7 @@ impl Def {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum Rel {
     LfLf(PreviewLogicFragmentId, PreviewLogicFragmentId, ImplicationRelation),
     OpOp(PreviewOperationId, PreviewOperationId, ImplicationRelation),
@@ -234,7 +264,7 @@ pub enum Rel {
     ProbPar(PreviewProblemId, PreviewParameterId, ParameterizedSolvability),
 }
 
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize)]
 pub enum RelKind {
     LfLf,
     OpOp,
@@ -275,13 +305,13 @@ impl Rel {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum RawFact {
     Def(Def),
     Rel(Rel),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum RawWroteStatus {
     Assumed,               // taken as given by HOPS, mainly due to being out of project's scope
     Conjectured,           // posed as an open problem
@@ -291,7 +321,7 @@ pub enum RawWroteStatus {
     TodoStatus,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum RawNotedSource {
     SrcText(String),         // outside of HOPS
     Source(PreviewSourceId), // in HOPS
@@ -299,11 +329,11 @@ pub enum RawNotedSource {
     SrcTodo,                 // waiting to be added to HOPS
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct RawSourceData {
     source: RawSource,
     factoids: Vec<RawWrote>,
-    drawings: Vec<Drawing>,
+    drawings: Vec<RawDrawing>,
 }
 
 impl RawSourceData {
@@ -344,15 +374,25 @@ impl RawSourceData {
 
     /// Notes that a source contains a hasse diagram of the listed sets.
     /// This method recreates that diagram with results from HOPS.
-    pub fn hasse(mut self, id: &str, page: Page, sets: Vec<Link>) -> Self {
-        self.drawings.push(Drawing::Hasse(sets));
+    pub fn hasse(mut self, id: &str, page: Page, parameters: Vec<PreviewParameterId>) -> Self {
+        self.drawings.push(RawDrawing {
+            id: DrawingId::new(id),
+            page,
+            kind: DrawingKind::Hasse,
+            parameters,
+        });
         self
     }
 
     /// Notes that a source has a complete comparison table of the listed sets.
     /// This recreates the same table from the results in HOPS.
-    pub fn table(mut self, id: &str, page: Page, sets: Vec<Link>) -> Self {
-        self.drawings.push(Drawing::Table(sets));
+    pub fn table(mut self, id: &str, page: Page, parameters: Vec<PreviewParameterId>) -> Self {
+        self.drawings.push(RawDrawing {
+            id: DrawingId::new(id),
+            page,
+            kind: DrawingKind::Table,
+            parameters,
+        });
         self
     }
 
@@ -26,6 +26,9 @@ mod input {
 }
 pub mod data {
     pub mod bibliography;
+    pub mod bit_matrix;
+    pub mod bound;
+    #[allow(clippy::module_inception)]
     pub mod data;
     pub mod date;
     pub mod digraph;
@@ -33,17 +36,31 @@ pub mod data {
     pub mod id;
     pub mod link;
     pub mod preview;
+    pub mod relation_index;
     pub mod score;
     pub mod simple_index;
 }
 mod work {
+    pub mod bridge;
+    pub mod classification;
+    pub mod closure;
     pub mod combine;
     pub mod compare;
+    pub mod contradiction;
     pub mod convert;
+    pub mod definition;
+    pub mod explain;
+    pub mod graph_relation;
     pub mod hide;
     pub mod hierarchy;
+    pub mod history;
+    pub mod incremental;
+    pub mod knowledge;
+    pub mod inference;
     pub mod preview_collection;
     pub mod processing;
+    pub mod solvability;
+    pub mod transfer;
 }
 mod output {
     pub mod api;
@@ -68,6 +85,8 @@ fn main() {
     computation.clear();
     let collection_fn = Box::new(crate::collection::build_collection);
     computation.retrieve_and_process_data(collection_fn);
+    computation.check_contradictions();
+    computation.find_redundant();
     computation.make_dots();
     computation.make_relation_table();
     computation.make_api();
@@ -3,13 +3,15 @@ use std::path::Path;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use crate::data::data::{Data, Parameter};
+use crate::data::data::{Data, KnowledgeState, Parameter, ProblemDefinition, SolvabilityOrigin};
 use crate::data::enums::*;
 use crate::data::id::*;
 use crate::data::preview;
-use crate::data::preview::PreviewParameter;
+use crate::data::preview::{HasPreview, PreviewParameter};
 use crate::general::file;
+use crate::input::source::{ClassicalSolvability, ParameterizedSolvability};
 use crate::work::combine;
+use crate::work::explain::{ProofBuilder, ProofTree};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SimpleApiSet {
@@ -24,6 +26,33 @@ pub struct SimpleApiRelation {
     pub superset_id: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiRelation {
+    pub subset_id: String,
+    pub superset_id: String,
+    pub proofs: Vec<ProofTree>,
+    pub tight: bool,
+    pub gap: Option<BoundGap>,
+    pub knowledge: KnowledgeState,
+}
+
+/// Complexity of a problem when restricted by `target_id`, as stated by `source_id`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiSolvability<T> {
+    pub target_id: String,
+    pub solvability: T,
+    pub source_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiProblem {
+    pub id: String,
+    pub name: String,
+    pub logic_fragment_id: Option<String>,
+    pub parameterized: Vec<ApiSolvability<ParameterizedSolvability>>,
+    pub classical: Vec<ApiSolvability<ClassicalSolvability>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SimpleApiData {
     pub date: String,
@@ -50,44 +79,39 @@ impl From<&(PreviewParameter, PreviewParameter)> for SimpleApiRelation {
     }
 }
 
+/// Ordered pairs of parameters with a known relation, each listed once.
+fn parameter_pairs(data: &Data) -> Vec<(&PreviewParameter, &PreviewParameter)> {
+    let mut res: Vec<(&PreviewParameter, &PreviewParameter)> = data
+        .arc_parameter_parameter
+        .iter()
+        .map(|(subset, superset, _)| (subset, superset))
+        .collect();
+    res.sort_by_key(|(subset, superset)| (subset.id.to_string(), superset.id.to_string()));
+    res.dedup_by_key(|(subset, superset)| (subset.id.clone(), superset.id.clone()));
+    res
+}
+
 impl From<&Data> for SimpleApiData {
     fn from(raw: &Data) -> Self {
-        // let init_relations = raw
-        //     .relations
-        //     .iter()
-        //     .filter_map(|r| match r.cpx {
-        //         SourcedCpxInfo::Equal { .. } | SourcedCpxInfo::Inclusion { .. } => {
-        //             Some(PreviewRelation {
-        //                 id: r.id.preview(),
-        //                 subset: r.subset.clone(),
-        //                 superset: r.superset.clone(),
-        //                 cpx: CpxInfo::Inclusion {
-        //                     mn: None,
-        //                     mx: Some(CpxTime::Linear),
-        //                 },
-        //             })
-        //         }
-        //         SourcedCpxInfo::Exclusion { .. } | SourcedCpxInfo::Unknown => None,
-        //     })
-        //     .collect();
-        // let preview_sets = raw.parameters.keys().cloned().collect();
-        // let relations = init_relations
-        //     .iter()
-        //     .map(|x| SimpleApiRelation {
-        //         subset_id: x.subset.id.to_string(),
-        //         superset_id: x.superset.id.to_string(),
-        //     })
-        //     .collect();
-        // let sets = raw
-        //     .parameters
-        //     .values()
-        //     .map(|x: &Parameter| x.preview())
-        //     .map(SimpleApiSet::from)
-        //     .collect();
+        let relations = parameter_pairs(raw)
+            .into_iter()
+            .filter(|(subset, superset)| {
+                matches!(
+                    raw.relation(&subset.id, &superset.id).forward_cpx(),
+                    CpxInfo::Equal | CpxInfo::Inclusion { mx: Some(_), .. }
+                )
+            })
+            .map(|(subset, superset)| SimpleApiRelation::from(&(subset.clone(), superset.clone())))
+            .collect();
+        let sets = raw
+            .parameters
+            .values()
+            .map(|x: &Parameter| SimpleApiSet::from(&x.preview()))
+            .collect();
         SimpleApiData {
             date: format!("{}", chrono::Local::now().format("%Y-%m-%d")),
-            sets: vec![], // todo
-            relations: vec![],
+            sets,
+            relations,
         }
     }
 }
@@ -100,6 +124,85 @@ pub fn create_simple_api(data: &Data, api_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Relations of all the pairs of parameters, a derivation is given once in the file and
+/// proofs of later relations refer to it by its handle.
+pub fn create_relation_api(data: &Data, api_dir: &Path) -> Result<()> {
+    let mut builder = ProofBuilder::default();
+    let relations: Vec<ApiRelation> = parameter_pairs(data)
+        .into_iter()
+        .map(|(subset, superset)| {
+            let relation = data.relation(&subset.id, &superset.id);
+            let info = relation.forward_cpx();
+            ApiRelation {
+                subset_id: subset.id.to_string(),
+                superset_id: superset.id.to_string(),
+                proofs: relation
+                    .forward
+                    .iter()
+                    .flat_map(|cpx| builder.relation_proofs(data, cpx))
+                    .collect(),
+                tight: info.is_tight(),
+                gap: info.gap(),
+                knowledge: data.parameter_knowledge(&subset.id, &superset.id),
+            }
+        })
+        .collect();
+    let serialized = serde_json::to_string(&relations)?;
+    let final_file = api_dir.join("relations.json");
+    file::write_file_content(&final_file, serialized.as_str())?;
+    Ok(())
+}
+
+fn origin_source_id(origin: &SolvabilityOrigin) -> String {
+    match origin {
+        SolvabilityOrigin::Directly(source)
+        | SolvabilityOrigin::AlongParameterBound { source, .. }
+        | SolvabilityOrigin::AlongInclusions { source, .. } => source.id.to_string(),
+    }
+}
+
+pub fn create_problem_api(data: &Data, api_dir: &Path) -> Result<()> {
+    let problems: Vec<ApiProblem> = data
+        .problems
+        .values()
+        .map(|problem| {
+            let this = problem.id.preview();
+            ApiProblem {
+                id: problem.id.to_string(),
+                name: problem.name_core.name.clone(),
+                logic_fragment_id: match &problem.definition {
+                    ProblemDefinition::Problem(_) => None,
+                    ProblemDefinition::ModelChecking(lf) => Some(lf.id.to_string()),
+                },
+                parameterized: data
+                    .arc_problem_parameter
+                    .iter()
+                    .filter(|(pr, _, _)| pr.id == this)
+                    .map(|(_, parameter, sourced)| ApiSolvability {
+                        target_id: parameter.id.to_string(),
+                        solvability: sourced.solvability.clone(),
+                        source_id: origin_source_id(&sourced.origin),
+                    })
+                    .collect(),
+                classical: data
+                    .arc_problem_gcprop
+                    .iter()
+                    .filter(|(pr, _, _)| pr.id == this)
+                    .map(|(_, property, sourced)| ApiSolvability {
+                        target_id: property.id.to_string(),
+                        solvability: sourced.solvability.clone(),
+                        source_id: origin_source_id(&sourced.origin),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    let serialized = serde_json::to_string_pretty(&problems)?;
+    let final_file = api_dir.join("problems.json");
+    file::write_file_content(&final_file, serialized.as_str())?;
+    Ok(())
+}
+
 pub fn create_set_api(data: &Data, api_dir: &Path) -> Result<()> {
     for parameter in data.parameters.values() {
         let serialized = serde_json::to_string_pretty(parameter)?;
@@ -1,3 +1,6 @@
+use crate::data::data::Data;
+use crate::data::enums::PairClass;
+use crate::data::id::PreviewParameterId;
 use crate::input::source::Cpx;
 
 pub fn interpolate_nums(from: u8, to: u8, ratio: f32) -> u8 {
@@ -37,6 +40,7 @@ pub enum Color {
     Magenta,
     Cyan,
     Lime,
+    Pink,
 }
 
 impl Color {
@@ -51,6 +55,7 @@ impl Color {
             Color::Magenta,
             Color::Cyan,
             Color::Lime,
+            Color::Pink,
         ]
     }
 
@@ -65,6 +70,7 @@ impl Color {
             Self::Magenta => "magenta",
             Self::Cyan => "cyan",
             Self::Lime => "lime",
+            Self::Pink => "pink",
         }
         .into()
     }
@@ -80,6 +86,7 @@ impl Color {
             "magenta" => Self::Magenta,
             "cyan" => Self::Cyan,
             "lime" => Self::Lime,
+            "pink" => Self::Pink,
             _ => panic!("color name {} not found", str),
         }
     }
@@ -95,6 +102,7 @@ impl Color {
             Self::Magenta => "#ee82ee",
             Self::Cyan => "#40e0d0",
             Self::Lime => "#00ff00",
+            Self::Pink => "#ff1493",
         }
         .into()
     }
@@ -110,6 +118,7 @@ impl Color {
             Self::Magenta => 0.6,
             Self::Cyan => 0.6,
             Self::Lime => 0.6,
+            Self::Pink => 0.5,
         }
     }
 
@@ -129,6 +138,16 @@ pub enum SimpleRelation {
     Unknown,
 }
 
+impl From<Option<bool>> for SimpleRelation {
+    fn from(bounded: Option<bool>) -> Self {
+        match bounded {
+            Some(true) => Self::Arrow,
+            Some(false) => Self::NotArrow,
+            None => Self::Unknown,
+        }
+    }
+}
+
 pub struct DirectedRelation {
     from: String,
     to: String,
@@ -136,6 +155,32 @@ pub struct DirectedRelation {
     to_from: SimpleRelation,
 }
 
+impl DirectedRelation {
+    /// Uses the classification of the pair stored in `data` and falls back
+    /// to the separate directions when only some of them are known.
+    pub fn new(data: &Data, from: &PreviewParameterId, to: &PreviewParameterId) -> Self {
+        let (from_to, to_from) = match data.pair_class(from, to) {
+            Some(PairClass::Equivalent) => (SimpleRelation::Arrow, SimpleRelation::Arrow),
+            Some(PairClass::StrictlyBounds) => (SimpleRelation::Arrow, SimpleRelation::NotArrow),
+            Some(PairClass::StrictlyBoundedBy) => (SimpleRelation::NotArrow, SimpleRelation::Arrow),
+            Some(PairClass::Incomparable) => (SimpleRelation::NotArrow, SimpleRelation::NotArrow),
+            None => {
+                let relation = data.relation(from, to);
+                (
+                    relation.forward_cpx().is_bounded().into(),
+                    relation.backward_cpx().is_bounded().into(),
+                )
+            }
+        };
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            from_to,
+            to_from,
+        }
+    }
+}
+
 pub fn relation_color(relation: DirectedRelation) -> Color {
     match (relation.from_to, relation.to_from) {
         (SimpleRelation::Arrow, SimpleRelation::Arrow) => Color::Yellow,
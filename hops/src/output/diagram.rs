@@ -5,17 +5,18 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time;
 
-use crate::data::data::{Data, Parameter};
+use crate::data::data::{Data, KnowledgeState, Named, Parameter};
 use crate::data::digraph::Edge;
 use crate::data::enums::*;
-use crate::data::id::{HasPreviewId, PreviewParameterId};
+use crate::data::id::{HasId, HasPreviewId, PreviewParameterId};
 use crate::data::preview::{HasPreview, PreviewParameter};
-use crate::data::score::has_better_score_than;
+use crate::data::score::{Score, has_better_score_than};
 use crate::general::file;
 use crate::input::source::Cpx;
 use crate::output::color::{Color, interpolate_colors};
 use crate::output::dot::{DotEdge, DotEdgeAttribute, DotGraph};
 use crate::output::markdown::Markdown;
+use crate::work::hide::filter_hidden;
 
 fn inclusion_edge_style(mx: &CpxTime) -> HashSet<DotEdgeAttribute> {
     let mut res: HashSet<DotEdgeAttribute> = HashSet::new();
@@ -53,6 +54,66 @@ fn inclusion_edge_style(mx: &CpxTime) -> HashSet<DotEdgeAttribute> {
     res
 }
 
+/// Edges of relations that are not proved are drawn fainter the less established they are.
+pub fn knowledge_edge_style(knowledge: KnowledgeState) -> HashSet<DotEdgeAttribute> {
+    let mut res: HashSet<DotEdgeAttribute> = HashSet::new();
+    match knowledge {
+        KnowledgeState::Proved | KnowledgeState::Folklore | KnowledgeState::UnknownToHOPS => {}
+        KnowledgeState::Mentioned => {
+            res.insert(DotEdgeAttribute::Style("dashed".into()));
+        }
+        KnowledgeState::Assumed => {
+            res.insert(DotEdgeAttribute::Style("dashed".into()));
+            res.insert(DotEdgeAttribute::Color(Color::Gray));
+        }
+        KnowledgeState::Conjectured | KnowledgeState::Disproved => {
+            res.insert(DotEdgeAttribute::Style("dotted".into()));
+            res.insert(DotEdgeAttribute::Color(Color::Gray));
+        }
+    }
+    res
+}
+
+/// Edges from every parameter to the parameters it upper bounds.
+pub fn parameter_edges(data: &Data) -> Vec<(String, String)> {
+    data.arc_parameter_parameter
+        .iter()
+        .filter(|(_, _, cpx)| CpxInfo::from(cpx.clone()).get_mx().is_some())
+        .map(|(f, t, _)| (f.id.to_string(), t.id.to_string()))
+        .collect()
+}
+
+pub fn parameter_edge_style(data: &Data, from: &str, to: &str) -> HashSet<DotEdgeAttribute> {
+    knowledge_edge_style(data.parameter_knowledge(&PreviewParameterId::from(from), &PreviewParameterId::from(to)))
+}
+
+/// Draws the items as a Hasse diagram, `labels` replace the names of some items
+/// and `style` gives the attributes of the edge between two items.
+pub fn hasse_diagram<T>(
+    name: &str,
+    items: &[&T],
+    edges: &[(String, String)],
+    labels: &HashMap<String, String>,
+    style: impl Fn(&str, &str) -> HashSet<DotEdgeAttribute>,
+) -> DotGraph<T>
+where
+    T: Named + HasId + Score,
+{
+    let mut digraph: DotGraph<T> = DotGraph::new(name, None);
+    let (shown, drawn_edges) = filter_hidden(items, edges);
+    for dp in shown {
+        match labels.get(&dp.id()) {
+            Some(label) => digraph.add_labelled_vertex(dp, label),
+            None => digraph.add_vertex(dp),
+        }
+    }
+    for (f, t) in drawn_edges {
+        let data = style(&f, &t);
+        digraph.add_edge(DotEdge { from: f, to: t, data });
+    }
+    digraph
+}
+
 // todo this was replaced with save_to_file within dotgraph, but the filtering logic should be
 // moved to main or auxiliary function to remove edges that are among vertices where both are not
 // in the diagram
@@ -150,6 +150,17 @@ where
         self.digraph.add_vertex(vertex.id.to_string(), vertex);
     }
 
+    /// Adds the vertex with `label` in place of its name.
+    pub fn add_labelled_vertex(&mut self, set: &T, label: &str) {
+        let mut vertex: DotVertex = DotVertex::from(set);
+        vertex.data.retain(|x| !matches!(x, DotVertexAttribute::Label(_)));
+        vertex.data.insert(DotVertexAttribute::Label(label.into()));
+        if let Some(f) = &self.info.color_fn {
+            vertex.data.insert(DotVertexAttribute::Color(f(set)));
+        }
+        self.digraph.add_vertex(vertex.id.to_string(), vertex);
+    }
+
     pub fn add_edge(&mut self, edge: DotEdge) {
         self.digraph.add_edge(edge);
     }
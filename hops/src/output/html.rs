@@ -58,6 +58,15 @@ pub fn colorbox_html(color: Color) -> String {
     format!("<span style=\"color:{}\">■</span>", color.hex())
 }
 
+/// Colorbox framed by the `highlight` color.
+pub fn highlighted_colorbox_html(color: Color, highlight: Color) -> String {
+    format!(
+        "<span style=\"color:{};outline:2px solid {}\">■</span>",
+        color.hex(),
+        highlight.hex()
+    )
+}
+
 pub fn link_html(content: &str, address: String) -> String {
     format!("<a href=\"{}\">{}</a>", address, content)
 }
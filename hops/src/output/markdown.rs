@@ -1,6 +1,6 @@
 //! Given the processed data generate markdown pages.
 
-use std::collections::{HashMap, LinkedList};
+use std::collections::{HashMap, HashSet, LinkedList};
 use std::env;
 use std::fmt;
 use std::fs;
@@ -14,6 +14,7 @@ use rand::seq::IndexedRandom;
 use regex::Regex;
 
 use crate::cli::paths::Paths;
+use crate::data::bound::Bound;
 use crate::data::data::*;
 use crate::data::enums::*;
 use crate::data::id::*;
@@ -23,9 +24,12 @@ use crate::data::score::Score;
 use crate::general::strings::nice_concat;
 use crate::general::worker::Worker;
 use crate::general::{file, progress};
-use crate::output::color::Color;
+use crate::output::color::{Color, DirectedRelation, relation_color};
+use crate::output::diagram::{hasse_diagram, parameter_edge_style, parameter_edges};
+use crate::output::dot::DotEdgeAttribute;
 use crate::output::html::*;
 use crate::output::to_markdown::ToMarkdown;
+use crate::work::explain::{ProofBuilder, ProofStyle, ProofTree};
 
 type Result<T> = std::result::Result<T, MarkdownError>;
 
@@ -95,19 +99,18 @@ impl GeneratedPage for Parameter {
                 .collect();
             res += &format!("tags: {}\n\n", tag_strings.join(", "));
         }
-        // let equivalent_strings: Vec<String> = self
-        //     .related_sets
-        //     .equivsets
-        //     .iter()
-        //     .filter(|x| x.id != self.id.preview())
-        //     .map(|x| builder.linkto(x))
-        //     .collect();
-        // if !equivalent_strings.is_empty() {
-        //     res += &format!(
-        //         "functionally equivalent to: {}\n\n",
-        //         equivalent_strings.join(", ")
-        //     );
-        // }
+        let equivalent_strings: Vec<String> = builder
+            .data
+            .equivalent_parameters(&self.id.preview())
+            .into_iter()
+            .map(|x| builder.linkto(&x.get_link()))
+            .collect();
+        if !equivalent_strings.is_empty() {
+            res += &format!(
+                "functionally equivalent to: {}\n\n",
+                equivalent_strings.join(", ")
+            );
+        }
         // if !self.providers.is_empty() {
         //     let provider_strings: Vec<String> =
         //         self.providers.iter().map(|x| builder.linkto(x)).collect();
@@ -147,9 +150,13 @@ impl GeneratedPage for Parameter {
             ParameterDefinition::IntersectionParameterGraphClass(param, gc) => {
                 format!("Intersection of [[{}]] and [[{}]]", param.id, gc.id)
             }
-            ParameterDefinition::FromParametricParameter(preview_parametric_parameter) => format!(
-                "concretization of {}",
-                preview_parametric_parameter.name_core.name
+            ParameterDefinition::FromParametricParameter(preview_parametric_parameter, value) => format!(
+                "concretization of {} at {}",
+                preview_parametric_parameter.name_core.name,
+                match value {
+                    Value::Value(x) => format!("${}$", x),
+                    Value::Infinity => "$\\infty$".into(),
+                }
             ),
         };
         res += &format!("**Definition:** {}\n\n", definition_string);
@@ -202,57 +209,151 @@ impl GeneratedPage for Parameter {
         // }
         // res += "\n";
         // }
-        // res += "---\n\n## Relations\n\n";
-        // let mut relation_table = Table::new(vec!["Other", "", "Relation from", "Relation to"]);
-        // for set in &builder.data.parameters {
-        //     let relation_fr_el: String =
-        //         match builder.data.get_relation(&set.preview(), &self.preview()) {
-        //             None => "unknown to HOPS".into(),
-        //             Some(rel) => {
-        //                 // let name = rel.preview().short_description(builder); // todo
-        //                 // format!("[{}]({})", name, rel.preview().get_url())
-        //                 rel.preview().short_description(builder)
-        //             }
-        //         };
-        //     let relation_to_el: String =
-        //         match builder.data.get_relation(&self.preview(), &set.preview()) {
-        //             None => "unknown to HOPS".into(),
-        //             Some(rel) => {
-        //                 // let name = rel.preview().short_description(builder);
-        //                 // format!("[{}]({})", name, rel.preview().get_url())
-        //                 rel.preview().short_description(builder)
-        //             }
-        //         };
-        //     let colorname = relation_color(&self.related_sets, set.id(), &set.preview()).name();
-        //     relation_table.add(vec![
-        //         builder.linkto(&set.preview()),
-        //         // the hidden span makes the color column sortable
-        //         format!(
-        //             "<span style=\"display:none\">{}</span>[[color {}]]",
-        //             colorname, colorname
-        //         ),
-        //         relation_fr_el,
-        //         relation_to_el,
-        //     ]);
-        // }
-        // res += builder.make_table(relation_table).as_str();
+        res += "---\n\n## Relations\n\n";
+        let mut relation_table = Table::new(vec!["Other", "Relation from", "Relation to"]);
+        let mut others: Vec<&Parameter> = builder.data.parameters.values().collect();
+        others.sort_by_key(|x| x.name_core.name.to_lowercase());
+        let mut open_questions = String::new();
+        for other in others {
+            if other.id == self.id {
+                continue;
+            }
+            let relation = builder.data.relation(&other.id.preview(), &self.id.preview());
+            let (forward, backward) = (relation.forward_cpx(), relation.backward_cpx());
+            let (other_id, this_id) = (other.id.preview(), self.id.preview());
+            relation_table.add(vec![
+                builder.linkto(&other.preview().get_link()),
+                with_knowledge(
+                    short_description(&forward, relation.forward_bound().as_ref()),
+                    builder.data.parameter_knowledge(&other_id, &this_id),
+                ),
+                with_knowledge(
+                    short_description(&backward, relation.backward_bound().as_ref()),
+                    builder.data.parameter_knowledge(&this_id, &other_id),
+                ),
+            ]);
+            let link = builder.linkto(&other.preview().get_link());
+            let this = builder.linkto(&self.preview().get_link());
+            if let Some(gap) = forward.gap() {
+                open_questions += &gap_description(&link, &this, &gap);
+            }
+            if let Some(gap) = backward.gap() {
+                open_questions += &gap_description(&this, &link, &gap);
+            }
+        }
+        res += builder.make_table(relation_table).as_str();
         res += "\n";
-        if !&self.timeline.is_empty() {
-            res += "---\n\n## Results\n\n";
-            for (source, wrotes) in &self.timeline {
-                if let Some(val) = source.to_markdown() {
-                    res += &val;
+        if !open_questions.is_empty() {
+            res += "---\n\n## Open questions\n\n";
+            res += &open_questions;
+            res += "\n";
+        }
+        let derivations = derivations_list(builder.data, &self.id.preview());
+        if !derivations.is_empty() {
+            res += "---\n\n## Derivations\n\n";
+            res += &derivations;
+        }
+        res += &timeline_markdown(&self.timeline);
+        res
+    }
+}
+
+/// Lists the results of every source in the order of the timeline.
+fn timeline_markdown(timeline: &[(PreviewSource, Vec<Wrote>)]) -> String {
+    if timeline.is_empty() {
+        return String::new();
+    }
+    let mut res = String::from("---\n\n## Results\n\n");
+    for (source, wrotes) in timeline {
+        res += &format!("### [[{}]]\n\n", source.id);
+        for wrote in wrotes {
+            if let Some(val) = wrote.to_markdown() {
+                res += &val;
+            }
+        }
+        res += "\n";
+    }
+    res
+}
+
+/// Redraws a figure of a source with the current results.
+fn drawing_markdown(builder: &Markdown, drawing: &Drawing, paths: &Paths) -> String {
+    let mut res = match drawing.page.to_markdown() {
+        Some(page) => format!("## Figure `{}` on {}\n\n", drawing.id, page),
+        None => format!("## Figure `{}`\n\n", drawing.id),
+    };
+    let data = builder.data;
+    let known_then: HashSet<(&PreviewParameterId, &PreviewParameterId)> =
+        drawing.known_then.iter().map(|(a, b, _)| (a, b)).collect();
+    let resolved_since = |a: &PreviewParameterId, b: &PreviewParameterId| {
+        !known_then.contains(&(a, b)) && data.relation(a, b).forward_cpx().is_bounded().is_some()
+    };
+    res += &format!(
+        "Relations resolved since the figure was published are highlighted in pink {}.\n\n",
+        colorbox_html(Color::Pink)
+    );
+    match drawing.kind {
+        DrawingKind::Hasse => {
+            let items: Vec<&Parameter> = drawing
+                .parameters
+                .iter()
+                .filter_map(|x| data.parameters.get(&x.id))
+                .collect();
+            let mut labels: HashMap<String, String> = HashMap::new();
+            for parameter in &drawing.parameters {
+                let mut names = vec![parameter.name_core.name.clone()];
+                for other in data.equivalent_parameters(&parameter.id) {
+                    if drawing.parameters.iter().any(|x| x.id == other.id) {
+                        names.push(other.name_core.name.clone());
+                    }
                 }
-                for wrote in wrotes {
-                    if let Some(val) = wrote.to_markdown() {
-                        res += &val;
+                labels.insert(parameter.id.to_string(), names.join(", "));
+            }
+            let digraph = hasse_diagram(
+                &format!("drawing_{}", drawing.id),
+                &items,
+                &parameter_edges(data),
+                &labels,
+                |f: &str, t: &str| {
+                    let mut style = parameter_edge_style(data, f, t);
+                    if resolved_since(&PreviewParameterId::from(f), &PreviewParameterId::from(t)) {
+                        style.retain(|x| !matches!(x, DotEdgeAttribute::Color(_)));
+                        style.insert(DotEdgeAttribute::Color(Color::Pink));
+                        style.insert(DotEdgeAttribute::PenWidth(200));
+                    }
+                    style
+                },
+            );
+            res += &include_dot_file(digraph.save_to_file(&paths.working_dir), &paths.final_dir);
+            res += "\n\n";
+        }
+        DrawingKind::Table => {
+            let numbers: Vec<String> = (1..=drawing.parameters.len()).map(|i| i.to_string()).collect();
+            let mut head = vec!["", "Parameter"];
+            head.extend(numbers.iter().map(|x| x.as_str()));
+            let mut table = Table::new(head);
+            for (i, row) in drawing.parameters.iter().enumerate() {
+                let mut cells = vec![(i + 1).to_string(), builder.linkto(&row.get_link())];
+                for column in &drawing.parameters {
+                    if column.id == row.id {
+                        cells.push(String::new());
+                    } else {
+                        // colored as in the legend, that is green when the column bounds the row
+                        let color = relation_color(DirectedRelation::new(data, &column.id, &row.id));
+                        if resolved_since(&column.id, &row.id) || resolved_since(&row.id, &column.id) {
+                            cells.push(highlighted_colorbox_html(color, Color::Pink));
+                        } else {
+                            cells.push(colorbox_html(color));
+                        }
                     }
                 }
+                table.add(cells);
             }
+            res += builder.make_table(table).as_str();
             res += "\n";
         }
-        res
     }
+    res
 }
 
 impl GeneratedPage for Source {
@@ -292,35 +393,93 @@ impl GeneratedPage for Source {
                 res += &format!("# Online source {}\n\n", self.id);
             }
         }
-        for (idx, drawing) in self.drawings.iter().enumerate() {
-            let name = &format!("drawing_{}_{}", self.id, idx);
-            match drawing {
-                Drawing::Table(list) => {
-                    // generate_relation_table(builder.data, list, paths, name, &builder.worker);
-                    // res += &format!("[[pdf ../{}.pdf]]\n\n", name);
-                }
-                Drawing::Hasse(list) => {
-                    // let drawing_path = make_drawing(
-                    //     builder.data,
-                    //     &paths.final_dir,
-                    //     name,
-                    //     &builder.data.get(list.clone()),
-                    //     None,
-                    // );
-                    // res += &include_dot_file(drawing_path, &paths.final_dir);
+        for drawing in &self.drawings {
+            res += &drawing_markdown(builder, drawing, paths);
+        }
+        if !self.wrote.is_empty() {
+            res += "\n## Results\n\n";
+            for wrote in &self.wrote {
+                if let Some(val) = wrote.to_markdown() {
+                    res += &val;
                 }
-            };
+            }
+            res += "\n";
         }
-        // res += &format!("{:?} {}", self.sourcekey, self.time);
-        // for s in &self.wrote {
-        //     if let Some(val) = s.to_markdown(builder) {
-        //         res += &format!("* {}\n", val);
-        //     }
-        // }
         res
     }
 }
 
+/// Relation of two parameters in a few words.
+fn short_description(cpx: &CpxInfo, bound: Option<&Bound>) -> String {
+    match cpx {
+        CpxInfo::Equal => "equal".into(),
+        CpxInfo::Inclusion { mn, mx } => {
+            let mut parts = vec![];
+            if let Some(mx) = mx.as_ref().and_then(|x| x.to_markdown()) {
+                match bound {
+                    Some(bound) => parts.push(format!("upper bound {} ${}$", mx, bound.to_latex())),
+                    None => parts.push(format!("upper bound {}", mx)),
+                }
+            }
+            if let Some(mn) = mn.as_ref().and_then(|x| x.to_markdown()) {
+                parts.push(format!("lower bound {}", mn));
+            }
+            if cpx.is_tight() {
+                parts.push("tight".into());
+            }
+            parts.join(", ")
+        }
+        CpxInfo::Exclusion => "exclusion".into(),
+        CpxInfo::Unknown => "unknown to HOPS".into(),
+    }
+}
+
+/// Tells whether the described relation is proved, folklore, assumed, conjectured, etc.
+fn with_knowledge(description: String, knowledge: KnowledgeState) -> String {
+    match knowledge.to_markdown() {
+        Some(state) if knowledge != KnowledgeState::UnknownToHOPS => format!("{} ({})", description, state),
+        _ => description,
+    }
+}
+
+/// List item asking for the best bound of `superset` by `subset` between the known ones.
+fn gap_description(subset: &str, superset: &str, gap: &BoundGap) -> String {
+    format!(
+        "* {} upper bounds {} by {} but only a lower bound of {} is known\n",
+        subset,
+        superset,
+        gap.upper.to_markdown().unwrap_or_default(),
+        gap.lower.to_markdown().unwrap_or_default()
+    )
+}
+
+/// Collapsible proofs of the inferred relations that involve the parameter.
+/// A derivation is given only once on the page, later proofs refer to it.
+fn derivations_list(data: &Data, id: &PreviewParameterId) -> String {
+    let mut builder = ProofBuilder::default();
+    let mut proofs: Vec<ProofTree> = vec![];
+    for (subset, superset, cpx) in &data.arc_parameter_parameter {
+        if (&subset.id != id && &superset.id != id) || subset.id == superset.id {
+            continue;
+        }
+        proofs.extend(
+            builder
+                .relation_proofs(data, cpx)
+                .into_iter()
+                .filter(|proof| !matches!(proof.created_by, CreatedBy::Directly(_))),
+        );
+    }
+    let mut res = String::new();
+    for (proof, formatted) in proofs.iter().zip(ProofTree::format_all(&proofs, data, ProofStyle::Markdown)) {
+        res += &format!(
+            "<details><summary>{}</summary>\n\n{}\n</details>\n\n",
+            proof.statement(data, ProofStyle::Markdown),
+            formatted
+        );
+    }
+    res
+}
+
 fn format_created_by(data: &Data, created_by: &CreatedBy) -> String {
     match &created_by {
         CreatedBy::TransferredFrom(transfer_group, handle) => {
@@ -332,6 +491,9 @@ fn format_created_by(data: &Data, created_by: &CreatedBy) -> String {
         CreatedBy::TransitiveExclusion(a, b) => {
             format!("by {} and {}", a, b)
         }
+        CreatedBy::TransitiveLowerBound(a, b) => {
+            format!("lower bound {} through linear bound {}", a, b)
+        }
         CreatedBy::ParallelComposition(a, b) => {
             format!("parallel composition of {} and {}", a, b)
         }
@@ -353,6 +515,12 @@ fn format_created_by(data: &Data, created_by: &CreatedBy) -> String {
         CreatedBy::Directly(source) => {
             format!("by [[{}]]", source.id)
         }
+        CreatedBy::ClassInclusion(source, showed_id) => {
+            format!("by inclusion of graph classes `{}` of [[{}]]", showed_id, source.id)
+        }
+        CreatedBy::ByDefinition(parameter) => {
+            format!("by definition of [[{}]]", parameter.id)
+        }
         CreatedBy::Todo => "todo".to_string(),
     }
 }
@@ -509,6 +677,83 @@ impl GeneratedPage for LogicFragment {
     }
 }
 
+/// Describes where a problem's result was stated and what carried it over.
+fn solvability_origin(builder: &Markdown, origin: &SolvabilityOrigin) -> String {
+    match origin {
+        SolvabilityOrigin::Directly(source) => builder.linkto(&source.get_link()),
+        SolvabilityOrigin::AlongParameterBound { parameter, source, .. } => format!(
+            "{} for {}",
+            builder.linkto(&source.get_link()),
+            builder.linkto(&parameter.get_link())
+        ),
+        SolvabilityOrigin::AlongInclusions { property, source, .. } => format!(
+            "{} for {}",
+            builder.linkto(&source.get_link()),
+            builder.linkto(&property.get_link())
+        ),
+    }
+}
+
+impl GeneratedPage for Problem {
+    fn get_page(&self, builder: &Markdown, _paths: &Paths) -> String {
+        let mut res = String::new();
+        if let Some(title) = self.name_core.to_markdown() {
+            res += &title;
+        }
+        let definition_string = match &self.definition {
+            ProblemDefinition::Problem(text) => text.clone(),
+            ProblemDefinition::ModelChecking(lf) => {
+                format!("model checking of {}", builder.linkto(&lf.get_link()))
+            }
+        };
+        res += &format!("**Definition:** {}\n\n", definition_string);
+        res += "[[handcrafted]]\n\n";
+        let this = self.id.preview();
+        let mut parameterized: Vec<_> = builder
+            .data
+            .arc_problem_parameter
+            .iter()
+            .filter(|(problem, _, _)| problem.id == this)
+            .collect();
+        if !parameterized.is_empty() {
+            parameterized.sort_by_key(|(_, parameter, _)| parameter.name_core.name.to_lowercase());
+            res += "---\n\n## Parameterized complexity\n\n";
+            let mut table = Table::new(vec!["Parameter", "Complexity", "Origin"]);
+            for (_, parameter, sourced) in parameterized {
+                table.add(vec![
+                    builder.linkto(&parameter.get_link()),
+                    sourced.solvability.to_markdown().unwrap(),
+                    solvability_origin(builder, &sourced.origin),
+                ]);
+            }
+            res += builder.make_table(table).as_str();
+            res += "\n";
+        }
+        let mut classical: Vec<_> = builder
+            .data
+            .arc_problem_gcprop
+            .iter()
+            .filter(|(problem, _, _)| problem.id == this)
+            .collect();
+        if !classical.is_empty() {
+            classical.sort_by_key(|(_, property, _)| property.name_core.name.to_lowercase());
+            res += "---\n\n## Complexity on graph classes\n\n";
+            let mut table = Table::new(vec!["Property", "Complexity", "Origin"]);
+            for (_, property, sourced) in classical {
+                table.add(vec![
+                    builder.linkto(&property.get_link()),
+                    sourced.solvability.to_markdown().unwrap(),
+                    solvability_origin(builder, &sourced.origin),
+                ]);
+            }
+            res += builder.make_table(table).as_str();
+            res += "\n";
+        }
+        res += &timeline_markdown(&self.timeline);
+        res
+    }
+}
+
 impl GeneratedPage for Operation {
     fn get_page(&self, _builder: &Markdown, _paths: &Paths) -> String {
         let mut res = String::new();
@@ -559,6 +804,14 @@ impl GeneratedPage for ParametricGraphClass {
             res += &format!("tags: {}\n\n", tag_strings.join(", "));
         }
         res += &format!("**Closed under:** {}\n\n", self.closed_under.name.name);
+        if !self.also_closed_under.is_empty() {
+            let implied: Vec<String> = self
+                .also_closed_under
+                .iter()
+                .map(|x| builder.linkto(&x.get_link()))
+                .collect();
+            res += &format!("**Hence also closed under:** {}\n\n", implied.join(", "));
+        }
         res += "[[handcrafted]]\n\n";
         res += "\n";
         res
@@ -602,6 +855,27 @@ impl GeneratedPage for GraphRelation {
             }
         };
         res += &format!("**Definition:** {}\n\n", def_string);
+        let id = self.id.preview();
+        let implies: Vec<String> = builder
+            .data
+            .arc_grrel_grrel
+            .iter()
+            .filter(|(a, _, _)| a.id == id)
+            .map(|(_, b, _)| builder.linkto(&b.get_link()))
+            .collect();
+        if !implies.is_empty() {
+            res += &format!("**Inclusion under this relation implies inclusion under:** {}\n\n", implies.join(", "));
+        }
+        let implied_by: Vec<String> = builder
+            .data
+            .arc_grrel_grrel
+            .iter()
+            .filter(|(_, b, _)| b.id == id)
+            .map(|(a, _, _)| builder.linkto(&a.get_link()))
+            .collect();
+        if !implied_by.is_empty() {
+            res += &format!("**Implied by inclusion under:** {}\n\n", implied_by.join(", "));
+        }
         res += "[[handcrafted]]\n\n";
         res += "\n";
         res
@@ -760,6 +1034,10 @@ impl<'a> Markdown<'a> {
                     let list = self.data.logic_fragments.values().collect::<Vec<&LogicFragment>>();
                     content += &self.simple_list_table("Logic Fragments", list);
                 }
+                "problems" => {
+                    let list = self.data.problems.values().collect::<Vec<&Problem>>();
+                    content += &self.simple_list_table("Problems", list);
+                }
                 "operations" => {
                     let list = self.data.operations.values().collect::<Vec<&Operation>>();
                     content += &self.simple_list_table("Operations", list);
@@ -1,9 +1,13 @@
 use std::fmt;
 
-use crate::data::data::{Fact, NameCore, Own, Wrote, WroteStatus};
+use crate::data::data::{Definition, Fact, KnowledgeState, NameCore, NotedSource, Own, Relation, Wrote, WroteStatus};
 use crate::data::enums::*;
 use crate::data::id::{HasId, PreviewShowedId};
 use crate::data::preview::{HasPreview, PreviewSource, PreviewSourceKey, PreviewTag};
+use crate::input::source::{
+    ClassicalSolvability, Cpx, ImplicationRelation, InclusionRelationUnderGraphRelation,
+    ParameterizedSolvability,
+};
 
 pub trait ToMarkdown {
     fn to_markdown(&self) -> Option<String>;
@@ -43,6 +47,41 @@ impl ToMarkdown for CpxTime {
     }
 }
 
+impl ToMarkdown for KnowledgeState {
+    fn to_markdown(&self) -> Option<String> {
+        Some(String::from(match self {
+            KnowledgeState::UnknownToHOPS => "unknown to HOPS",
+            KnowledgeState::Conjectured => "conjectured",
+            KnowledgeState::Mentioned => "mentioned",
+            KnowledgeState::Proved => "proved",
+            KnowledgeState::Folklore => "folklore",
+            KnowledgeState::Assumed => "assumed",
+            KnowledgeState::Disproved => "disproved",
+        }))
+    }
+}
+
+impl ToMarkdown for ParameterizedSolvability {
+    fn to_markdown(&self) -> Option<String> {
+        Some(String::from(match self {
+            ParameterizedSolvability::Polynomial => "polynomial",
+            ParameterizedSolvability::Fpt => "FPT",
+            ParameterizedSolvability::Xp => "XP",
+            ParameterizedSolvability::Whard => "W[1]-hard",
+            ParameterizedSolvability::ParaNpHard => "para-NP-hard",
+        }))
+    }
+}
+
+impl ToMarkdown for ClassicalSolvability {
+    fn to_markdown(&self) -> Option<String> {
+        Some(String::from(match self {
+            ClassicalSolvability::Polynomial => "polynomial",
+            ClassicalSolvability::NpHard => "NP-hard",
+        }))
+    }
+}
+
 impl ToMarkdown for NameCore {
     fn to_markdown(&self) -> Option<String> {
         let mut res = String::new();
@@ -294,15 +333,17 @@ impl ToMarkdown for PreviewSource {
 }
 
 impl ToMarkdown for Wrote {
+    /// A list item with the text followed by a nested item for every fact.
     fn to_markdown(&self) -> Option<String> {
-        let mut res = String::new();
-        if let Some(page) = self.page.to_markdown(){ 
-            res += &format!("{} : ", page);
+        let mut res = String::from("* ");
+        if let Some(page) = self.page.to_markdown() {
+            res += &format!("{}: ", page);
         }
         res += &self.text;
+        res += "\n";
         for fact in &self.facts {
             if let Some(facttext) = fact.to_markdown() {
-                res += &facttext;
+                res += &format!("    * {}\n", facttext);
             }
         }
         Some(res)
@@ -311,28 +352,142 @@ impl ToMarkdown for Wrote {
 
 impl ToMarkdown for (PreviewShowedId, WroteStatus, Fact) {
     fn to_markdown(&self) -> Option<String> {
-        let mut res = String::new();
         let (showedid, status, fact) = self;
-        res += match fact {
-            Fact::Definition(definition) => "definition",
-            Fact::Relation(relation) => "relation",
-        };
-        // Fact::Relation(status, relation) => {
-        //     if let Some(val) = relation.long_description() {
-        //         res += &val;
-        //     }
-        // }
-        // Fact::Definition(status, preview_set) => {
-        //     let set = builder.data.get_set_by_id(preview_set); // todo builder was removed
-        //     from parameters
-        //     if let Some(val) = set.preview().to_markdown() {
-        //         res += &val;
-        //     }
-        // }
+        let mut res = format!("`{}` {}", showedid, fact.to_markdown()?);
+        if let Some(status) = status.to_markdown() {
+            res += &format!(" ({})", status);
+        }
         Some(res)
     }
 }
 
+impl ToMarkdown for WroteStatus {
+    fn to_markdown(&self) -> Option<String> {
+        Some(match self {
+            WroteStatus::Assumed => "assumed".into(),
+            WroteStatus::Conjectured => "conjectured".into(),
+            WroteStatus::Original => "original".into(),
+            WroteStatus::Derivative => "derivative".into(),
+            WroteStatus::Noted(NotedSource::Source(source)) => format!("noted from [[{}]]", source.id),
+            WroteStatus::Noted(NotedSource::SrcText(text)) => format!("noted from {}", text),
+            WroteStatus::Noted(NotedSource::Omitted | NotedSource::SrcTodo) => "noted".into(),
+            WroteStatus::TodoStatus => return None,
+        })
+    }
+}
+
+impl ToMarkdown for Fact {
+    fn to_markdown(&self) -> Option<String> {
+        match self {
+            Fact::Definition(definition) => definition.to_markdown(),
+            Fact::Relation(relation) => relation.to_markdown(),
+        }
+    }
+}
+
+impl ToMarkdown for Definition {
+    fn to_markdown(&self) -> Option<String> {
+        let id = match self {
+            Definition::LogicFragment(x) => x.id.to_string(),
+            Definition::Parameter(x) => x.id.to_string(),
+            Definition::Graph(x) => x.id.to_string(),
+            Definition::GraphClass(x) => x.id.to_string(),
+            Definition::Operation(x) => x.id.to_string(),
+            Definition::Problem(x) => x.id.to_string(),
+            Definition::ParParameter(x) => x.id.to_string(),
+            Definition::ParGraphClass(x) => x.id.to_string(),
+            Definition::Property(x) => x.id.to_string(),
+        };
+        Some(format!("definition of [[{}]]", id))
+    }
+}
+
+/// Sentence stating that `a` and `b` are in the given implication relation.
+fn implication(a: String, b: String, relation: &ImplicationRelation) -> String {
+    match relation {
+        ImplicationRelation::Equivalent => format!("[[{}]] is equivalent to [[{}]]", a, b),
+        ImplicationRelation::Implies => format!("[[{}]] implies [[{}]]", a, b),
+        ImplicationRelation::Excludes => format!("[[{}]] excludes [[{}]]", a, b),
+        ImplicationRelation::Unknown => format!("relation of [[{}]] and [[{}]] is unknown", a, b),
+    }
+}
+
+fn inclusion(a: String, b: String, relation: &InclusionRelationUnderGraphRelation) -> String {
+    format!(
+        "{} under [[{}]]",
+        implication(a, b, &relation.relation),
+        relation.graph_relation
+    )
+}
+
+fn complexity(a: String, b: String, cpx: &Cpx) -> String {
+    let cpx_time = |time: &CpxTime| time.to_markdown().unwrap();
+    match cpx {
+        Cpx::Bounds(mn, mx) => format!(
+            "[[{}]] upper bounds [[{}]] by {} and lower bounds it by {}",
+            a,
+            b,
+            cpx_time(mx),
+            cpx_time(mn)
+        ),
+        Cpx::UpperBound(mx) => format!("[[{}]] upper bounds [[{}]] by {}", a, b, cpx_time(mx)),
+        Cpx::UpperBoundBy(bound) => {
+            format!("[[{}]] upper bounds [[{}]] by ${}$", a, b, bound.to_latex())
+        }
+        Cpx::LowerBound(mn) => format!("[[{}]] lower bounds [[{}]] by {}", a, b, cpx_time(mn)),
+        Cpx::StrictUpperBound(mx) => format!(
+            "[[{}]] upper bounds [[{}]] by {} but not the other way around",
+            a,
+            b,
+            cpx_time(mx)
+        ),
+        Cpx::Exactly(time) => format!("[[{}]] bounds [[{}]] by exactly {}", a, b, cpx_time(time)),
+        Cpx::Equivalent(forward, backward) => format!(
+            "[[{}]] upper bounds [[{}]] by {} and is upper bounded by it by {}",
+            a,
+            b,
+            cpx_time(forward),
+            cpx_time(backward)
+        ),
+        Cpx::Equal => format!("[[{}]] is equal to [[{}]]", a, b),
+        Cpx::Exclusion => format!("[[{}]] does not upper bound [[{}]]", a, b),
+        Cpx::Incomparable => format!("[[{}]] and [[{}]] are incomparable", a, b),
+        Cpx::Todo => format!("relation of [[{}]] and [[{}]]", a, b),
+    }
+}
+
+impl ToMarkdown for Relation {
+    fn to_markdown(&self) -> Option<String> {
+        Some(match self {
+            Relation::LfLf(a, b, r) => implication(a.id.to_string(), b.id.to_string(), r),
+            Relation::OpOp(a, b, r) => implication(a.id.to_string(), b.id.to_string(), r),
+            Relation::GrGr(a, b, r) => inclusion(a.id.to_string(), b.id.to_string(), r),
+            Relation::GcGc(a, b, r) => inclusion(a.id.to_string(), b.id.to_string(), r),
+            Relation::GrGc(a, b, r) => inclusion(a.id.to_string(), b.id.to_string(), r),
+            Relation::PgcPgc(a, b, r) => implication(a.id.to_string(), b.id.to_string(), r),
+            Relation::ParPar(a, b, cpx) => complexity(a.id.to_string(), b.id.to_string(), cpx),
+            Relation::PropProp(a, b, r) => implication(a.id.to_string(), b.id.to_string(), r),
+            Relation::PropPar(a, b, r) => implication(a.id.to_string(), b.id.to_string(), r),
+            Relation::GcPar(a, b, r) => implication(a.id.to_string(), b.id.to_string(), r),
+            Relation::GcProp(a, b, _) => format!("[[{}]] is equivalent to [[{}]]", a.id, b.id),
+            Relation::ParProp(a, b, _) => format!("[[{}]] is equivalent to [[{}]]", a.id, b.id),
+            Relation::ProbProb(a, b, r) => implication(a.id.to_string(), b.id.to_string(), r),
+            Relation::ProbProp(problem, property, solvability) => format!(
+                "[[{}]] is {} on [[{}]]",
+                problem.id,
+                solvability.to_markdown().unwrap(),
+                property.id
+            ),
+            Relation::ProbPar(problem, parameter, solvability) => format!(
+                "[[{}]] is {} when parameterized by [[{}]]",
+                problem.id,
+                solvability.to_markdown().unwrap(),
+                parameter.id
+            ),
+        })
+    }
+}
+
 // impl ToMarkdown for SourceSubset {
 //     fn to_markdown(&self) -> Option<String> {
 //         let mut res = String::new();
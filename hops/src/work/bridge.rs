@@ -0,0 +1,127 @@
+//! Connects the hierarchy of graph classes with the hierarchy of parameters.
+//!
+//! A class has bounded p and p upper bounds q, so the class has bounded q as well.
+//! Classes defined as "graphs where p is bounded" are ordered like their parameters.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::data::*;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::input::source::{ImplicationRelation, InclusionRelationUnderGraphRelation};
+use crate::work::graph_relation::GcGcArcs;
+use crate::work::inference::ParameterArcs;
+
+pub type GcParArcs = Vec<(PreviewGraphClass, PreviewParameter, ImplicationRelation)>;
+
+fn upper_bounds(cpx: &SourcedCpxInfo) -> bool {
+    matches!(
+        cpx,
+        SourcedCpxInfo::Equal { .. } | SourcedCpxInfo::Inclusion { mx: Some(_), .. }
+    )
+}
+
+/// Classes defined by a bounded parameter.
+pub fn bounded_classes(graph_classes: &[GraphClass]) -> Vec<(PreviewGraphClass, PreviewParameter)> {
+    graph_classes
+        .iter()
+        .filter_map(|gc| match &gc.definition {
+            GraphClassDefinition::Parameter(parameter) => Some((gc.preview(), parameter.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Adds the bounded parameters of classes implied by definitions and by the bounds between
+/// parameters. Unbounded parameters move the other way, if q is unbounded then so is
+/// every p that upper bounds q.
+pub fn bridge_gc_par(
+    arc_gc_par: GcParArcs,
+    graph_classes: &[GraphClass],
+    arc_parameter_parameter: &ParameterArcs,
+) -> GcParArcs {
+    let mut seen: HashSet<(PreviewGraphClassId, PreviewParameterId, ImplicationRelation)> = arc_gc_par
+        .iter()
+        .map(|(gc, par, rel)| (gc.id.clone(), par.id.clone(), rel.clone()))
+        .collect();
+    let mut res = arc_gc_par;
+    for (gc, parameter) in bounded_classes(graph_classes) {
+        if seen.insert((gc.id.clone(), parameter.id.clone(), ImplicationRelation::Implies)) {
+            res.push((gc, parameter, ImplicationRelation::Implies));
+        }
+    }
+    let mut derived: GcParArcs = vec![];
+    for (gc, parameter, relation) in &res {
+        for (subset, superset, cpx) in arc_parameter_parameter {
+            if subset.id == superset.id || !upper_bounds(cpx) {
+                continue;
+            }
+            let (target, relation) = match relation {
+                ImplicationRelation::Implies | ImplicationRelation::Equivalent
+                    if subset.id == parameter.id =>
+                {
+                    (superset, ImplicationRelation::Implies)
+                }
+                ImplicationRelation::Excludes if superset.id == parameter.id => {
+                    (subset, ImplicationRelation::Excludes)
+                }
+                _ => continue,
+            };
+            if seen.insert((gc.id.clone(), target.id.clone(), relation.clone())) {
+                derived.push((gc.clone(), target.clone(), relation));
+            }
+        }
+    }
+    res.extend(derived);
+    res
+}
+
+/// Inclusions between classes defined by bounded parameters. If a upper bounds b then
+/// graphs with bounded a have bounded b, if a does not upper bound b then some graphs
+/// with bounded a have unbounded b. The inclusions are plain, so they are stated under
+/// `graph_relation` like the stated plain inclusions.
+pub fn bridge_gc_gc(
+    graph_classes: &[GraphClass],
+    arc_parameter_parameter: &ParameterArcs,
+    graph_relation: &PreviewGraphRelationId,
+) -> GcGcArcs {
+    let bounded = bounded_classes(graph_classes);
+    let by_parameter: HashMap<&PreviewParameterId, Vec<&PreviewGraphClass>> =
+        bounded.iter().fold(HashMap::new(), |mut acc, (gc, parameter)| {
+            acc.entry(&parameter.id).or_default().push(gc);
+            acc
+        });
+    let mut res: GcGcArcs = vec![];
+    for (subset, superset, cpx) in arc_parameter_parameter {
+        if subset.id == superset.id {
+            continue;
+        }
+        let relation = match cpx {
+            _ if upper_bounds(cpx) => ImplicationRelation::Implies,
+            SourcedCpxInfo::Exclusion { .. } => ImplicationRelation::Excludes,
+            _ => continue,
+        };
+        let (Some(from), Some(to)) = (by_parameter.get(&subset.id), by_parameter.get(&superset.id))
+        else {
+            continue;
+        };
+        for a in from {
+            for b in to {
+                res.push((
+                    (*a).clone(),
+                    (*b).clone(),
+                    InclusionRelationUnderGraphRelation {
+                        relation: relation.clone(),
+                        graph_relation: graph_relation.clone(),
+                    },
+                ));
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/bridge.rs"]
+mod tests;
@@ -0,0 +1,39 @@
+//! Classifies pairs of parameters as equivalent, strict or incomparable once the bounds
+//! in both directions are inferred, so that it need not be stated by hand.
+
+use std::collections::HashMap;
+
+use crate::data::enums::*;
+use crate::data::id::PreviewParameterId;
+use crate::data::preview::PreviewParameter;
+
+/// Classification of every ordered pair of distinct parameters with both directions known.
+pub fn classify_pairs(
+    arc_parameter_parameter: &[(PreviewParameter, PreviewParameter, SourcedCpxInfo)],
+) -> Vec<(PreviewParameter, PreviewParameter, PairClass)> {
+    let bounded: HashMap<(&PreviewParameterId, &PreviewParameterId), bool> = arc_parameter_parameter
+        .iter()
+        .filter(|(a, b, _)| a.id != b.id)
+        .filter_map(|(a, b, cpx)| Some(((&a.id, &b.id), CpxInfo::from(cpx.clone()).is_bounded()?)))
+        .collect();
+    let mut res: Vec<(PreviewParameter, PreviewParameter, PairClass)> = arc_parameter_parameter
+        .iter()
+        .filter_map(|(a, b, _)| {
+            let forward = *bounded.get(&(&a.id, &b.id))?;
+            let backward = *bounded.get(&(&b.id, &a.id))?;
+            let class = match (forward, backward) {
+                (true, true) => PairClass::Equivalent,
+                (true, false) => PairClass::StrictlyBounds,
+                (false, true) => PairClass::StrictlyBoundedBy,
+                (false, false) => PairClass::Incomparable,
+            };
+            Some((a.clone(), b.clone(), class))
+        })
+        .collect();
+    res.sort_by_key(|(a, b, _)| (a.id.to_string(), b.id.to_string()));
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/classification.rs"]
+mod tests;
@@ -0,0 +1,261 @@
+//! Closure of the relations between parameters computed on bit matrices.
+//!
+//! Parameters are interned into indices and for every `CpxTime` we keep the pairs whose
+//! upper bound is at most that time. A round composes the matrices and reports every pair
+//! that improves along with a witness, so that the caller can record how it was derived.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::bit_matrix::BitMatrix;
+use crate::data::enums::*;
+use crate::data::id::PreviewParameterId;
+use crate::work::inference::{PartialResult, WorkRelation};
+
+const TIMES: [CpxTime; 6] = [
+    CpxTime::Constant,
+    CpxTime::Linear,
+    CpxTime::Polynomial,
+    CpxTime::Exponential,
+    CpxTime::Tower,
+    CpxTime::Exists,
+];
+
+fn level(time: &CpxTime) -> usize {
+    TIMES.iter().position(|x| x == time).unwrap()
+}
+
+/// Pairs of upper bound levels whose composition is bounded by the level of the result.
+/// Constant composed with anything is constant and exponential composed with anything
+/// else than constant is a tower, see `combine_serial`.
+const COMPOSITIONS: [(usize, &[(usize, usize)]); 5] = [
+    (0, &[(0, 5), (5, 0)]),
+    (1, &[(1, 1)]),
+    (2, &[(2, 2)]),
+    (4, &[(4, 4)]),
+    (5, &[(5, 5)]),
+];
+
+/// Maps parameters to indices of the matrices.
+#[derive(Debug)]
+pub struct Interner {
+    ids: Vec<PreviewParameterId>,
+    index: HashMap<PreviewParameterId, usize>,
+}
+
+impl Interner {
+    pub fn new<'a>(ids: impl IntoIterator<Item = &'a PreviewParameterId>) -> Self {
+        let mut ids: Vec<PreviewParameterId> = ids.into_iter().cloned().collect();
+        ids.sort_by_key(|x| x.to_string());
+        let index = ids.iter().enumerate().map(|(i, x)| (x.clone(), i)).collect();
+        Self { ids, index }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn relation(&self, (a, b): (usize, usize)) -> WorkRelation {
+        WorkRelation::new(&self.ids[a], &self.ids[b])
+    }
+}
+
+/// How an improved relation follows from the relations known at the start of the round.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Derivation {
+    /// Upper bounds of the first and the second pair compose into an upper bound of the third one.
+    Transitive((usize, usize), (usize, usize), (usize, usize)),
+    /// The relation of the first pair carries over to the third one as the second pair is equal.
+    ThroughEquivalence((usize, usize), (usize, usize), (usize, usize)),
+    /// Upper bound of the first pair and exclusion of the second one give an exclusion of the third one.
+    Exclusion((usize, usize), (usize, usize), (usize, usize)),
+    /// Lower bound of the first pair carries over to the third one as the second pair is bounded linearly.
+    LowerBound((usize, usize), (usize, usize), (usize, usize)),
+}
+
+/// Everything known about the pairs, upper and lower bounds are indexed by `TIMES`.
+struct Matrices {
+    /// Pairs with an upper bound at most the time.
+    upper: Vec<BitMatrix>,
+    /// Pairs with a lower bound at least the time.
+    lower: Vec<BitMatrix>,
+    equal: BitMatrix,
+    exclusion: BitMatrix,
+}
+
+impl Matrices {
+    fn new(interner: &Interner, relations: &HashMap<WorkRelation, PartialResult>) -> Self {
+        let n = interner.len();
+        let mut upper = vec![BitMatrix::new(n); TIMES.len()];
+        let mut lower = vec![BitMatrix::new(n); TIMES.len()];
+        let mut equal = BitMatrix::new(n);
+        let mut exclusion = BitMatrix::new(n);
+        for (relation, result) in relations {
+            let (Some(&a), Some(&b)) = (
+                interner.index.get(&relation.subset),
+                interner.index.get(&relation.superset),
+            ) else {
+                continue;
+            };
+            if a == b {
+                continue;
+            }
+            match &result.cpx {
+                CpxInfo::Equal => equal.set(a, b),
+                CpxInfo::Inclusion { mn, mx } => {
+                    if let Some(mx) = mx {
+                        upper[level(mx)..].iter_mut().for_each(|m| m.set(a, b));
+                    }
+                    if let Some(mn) = mn {
+                        lower[..=level(mn)].iter_mut().for_each(|m| m.set(a, b));
+                    }
+                }
+                CpxInfo::Exclusion => exclusion.set(a, b),
+                CpxInfo::Unknown => {}
+            }
+        }
+        Self {
+            upper,
+            lower,
+            equal,
+            exclusion,
+        }
+    }
+
+    /// Pairs whose relation implies an upper bound of the time, an equality is compatible
+    /// with any upper bound but a constant one.
+    fn upper_known(&self, t: usize) -> BitMatrix {
+        match t < level(&CpxTime::Linear) {
+            true => self.upper[t].clone(),
+            false => self.upper[t].union(&self.equal),
+        }
+    }
+
+    /// Pairs whose relation implies a lower bound of the time, an equality is compatible with
+    /// a lower bound of at most linear time and an exclusion with any lower bound.
+    fn lower_known(&self, t: usize) -> BitMatrix {
+        let known = self.lower[t].union(&self.exclusion);
+        match t > level(&CpxTime::Linear) {
+            true => known,
+            false => known.union(&self.equal),
+        }
+    }
+}
+
+/// Pairs of `product` missing in `known` along with the witness found by `witness`.
+fn improvements(
+    product: &BitMatrix,
+    known: &BitMatrix,
+    witness: impl Fn(usize, usize) -> Option<Derivation>,
+) -> Vec<Derivation> {
+    let new = product.difference(known).without_diagonal();
+    (0..new.size())
+        .flat_map(|a| new.ones(a).map(move |b| (a, b)))
+        .filter_map(|(a, b)| witness(a, b))
+        .collect()
+}
+
+/// Relations that improve by composing the `relations` once.
+pub fn derivations(interner: &Interner, relations: &HashMap<WorkRelation, PartialResult>) -> Vec<Derivation> {
+    let m = Matrices::new(interner, relations);
+    let upper_t: Vec<BitMatrix> = m.upper.iter().map(|x| x.transpose()).collect();
+    let mut res = vec![];
+    let mut found = BitMatrix::new(interner.len());
+    for (target, pairs) in COMPOSITIONS {
+        for &(first, second) in pairs {
+            let product = m.upper[first].product(&m.upper[second]);
+            let known = m.upper_known(target).union(&found);
+            let derived = improvements(&product, &known, |a, b| {
+                let c = BitMatrix::first_common(m.upper[first].row(a), upper_t[second].row(b))?;
+                Some(Derivation::Transitive((a, c), (c, b), (a, b)))
+            });
+            for derivation in derived {
+                if let Derivation::Transitive(_, _, (a, b)) = derivation {
+                    found.set(a, b);
+                }
+                res.push(derivation);
+            }
+        }
+    }
+    res.extend(lower_bounds(&m));
+    let equal_t = m.equal.transpose();
+    let mut copies: HashSet<((usize, usize), (usize, usize))> = HashSet::new();
+    let features = m.upper.iter().enumerate().map(|(t, x)| (x, m.upper_known(t)));
+    let features = features.chain(m.lower.iter().enumerate().map(|(t, x)| (x, m.lower_known(t))));
+    let features = features.chain([(&m.equal, m.equal.clone()), (&m.exclusion, m.exclusion.clone())]);
+    for (feature, known) in features {
+        let feature_t = feature.transpose();
+        let right = improvements(&feature.product(&m.equal), &known, |z, y| {
+            let x = BitMatrix::first_common(feature.row(z), equal_t.row(y))?;
+            Some(Derivation::ThroughEquivalence((z, x), (x, y), (z, y)))
+        });
+        let left = improvements(&equal_t.product(feature), &known, |y, z| {
+            let x = BitMatrix::first_common(equal_t.row(y), feature_t.row(z))?;
+            Some(Derivation::ThroughEquivalence((x, z), (x, y), (y, z)))
+        });
+        for derivation in right.into_iter().chain(left) {
+            if let Derivation::ThroughEquivalence(copied, _, target) = derivation
+                && copies.insert((copied, target))
+            {
+                res.push(derivation);
+            }
+        }
+    }
+    let (included, included_t) = (&m.upper[level(&CpxTime::Exists)], &upper_t[level(&CpxTime::Exists)]);
+    let exclusion_t = m.exclusion.transpose();
+    let by_superset = improvements(&m.exclusion.product(included_t), &m.exclusion, |e, f| {
+        let d = BitMatrix::first_common(m.exclusion.row(e), included.row(f))?;
+        Some(Derivation::Exclusion((f, d), (e, d), (e, f)))
+    });
+    let by_subset = improvements(&included_t.product(&m.exclusion), &m.exclusion, |e, f| {
+        let c = BitMatrix::first_common(included_t.row(e), exclusion_t.row(f))?;
+        Some(Derivation::Exclusion((c, e), (c, f), (e, f)))
+    });
+    let mut excluded: HashSet<(usize, usize)> = HashSet::new();
+    for derivation in by_superset.into_iter().chain(by_subset) {
+        if let Derivation::Exclusion(_, _, target) = derivation
+            && excluded.insert(target)
+        {
+            res.push(derivation);
+        }
+    }
+    res
+}
+
+/// Lower bounds that follow from a lower bound and a linear upper bound. If `a` cannot bound
+/// `b` below time t and `c` bounds `b` linearly, then `a` cannot bound `c` below t either.
+/// Likewise, if `c` is bounded linearly by `a`, then `c` cannot bound `b` below t.
+fn lower_bounds(m: &Matrices) -> Vec<Derivation> {
+    let linear = m.upper[level(&CpxTime::Linear)].difference(&m.upper[level(&CpxTime::Constant)]);
+    let linear_t = linear.transpose();
+    let mut found = BitMatrix::new(m.equal.size());
+    let mut res = vec![];
+    for t in (level(&CpxTime::Linear)..TIMES.len()).rev() {
+        let lower_t = m.lower[t].transpose();
+        let known = m.lower_known(t).union(&found);
+        let by_superset = improvements(&m.lower[t].product(&linear_t), &known, |a, c| {
+            let b = BitMatrix::first_common(m.lower[t].row(a), linear.row(c))?;
+            Some(Derivation::LowerBound((a, b), (c, b), (a, c)))
+        });
+        let by_subset = improvements(&linear_t.product(&m.lower[t]), &known, |c, b| {
+            let a = BitMatrix::first_common(linear_t.row(c), lower_t.row(b))?;
+            Some(Derivation::LowerBound((a, b), (a, c), (c, b)))
+        });
+        for derivation in by_superset.into_iter().chain(by_subset) {
+            if let Derivation::LowerBound(_, _, (x, y)) = derivation
+                && !found.get(x, y)
+            {
+                found.set(x, y);
+                res.push(derivation);
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/closure.rs"]
+mod tests;
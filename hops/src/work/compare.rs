@@ -1,108 +1,111 @@
 use crate::data::enums::*;
+use crate::work::combine::CombinationError;
+use crate::work::inference::PartialResult;
 
-// impl CpxTime {
-//     pub fn num(&self) -> u32 {
-//         match self {
-//             Constant => 0,
-//             Linear => 1,
-//             Polynomial => 2,
-//             Exponential => 3,
-//             Tower => 4,
-//             Exists => 5,
-//         }
-//     }
-//
-//     pub fn is_smaller_than(&self, other: &Self) -> bool {
-//         self.num() < other.num()
-//     }
-// }
-//
-// impl SourcedCpxInfo {
-//     fn num(&self) -> u32 {
-//         match self {
-//             Self::Equal { .. } => 0,
-//             Self::Inclusion { .. } => 1,
-//             Self::Exclusion { .. } => 2,
-//             Self::Unknown => 3,
-//         }
-//     }
-//
-//     pub fn compare_to(&self, other: &SourcedCpxInfo) -> ComparisonResult {
-//         let (a, b, should_flip_result) = if self.num() < other.num() {
-//             (self, other, false)
-//         } else {
-//             (other, self, true)
-//         };
-//         let res: ComparisonResult = match (a, b) {
-//             (Self::Equal { .. }, Self::Equal { .. }) => ComparisonResult::Equivalent,
-//             (Self::Equal { .. }, Self::Inclusion { .. }) => ComparisonResult::Better,
-//             (Self::Equal { .. }, Self::Exclusion { .. }) => panic!("impossible {:?} {:?}", a, b),
-//             (Self::Equal { .. }, Self::Unknown) => ComparisonResult::Better,
-//             (Self::Inclusion { .. }, Self::Exclusion { .. }) => {
-//                 panic!("impossible {:?} {:?}", a, b)
-//             }
-//             (Self::Inclusion { .. }, Self::Unknown) => ComparisonResult::Better,
-//             (Self::Inclusion { mn: mna, mx: mxa }, Self::Inclusion { mn: mnb, mx: mxb }) => {
-//                 let res_mn: ComparisonResult = match (mna, mnb) {
-//                     (Some((a, sa)), Some((b, sb))) => {
-//                         if a == b {
-//                             ComparisonResult::Equivalent
-//                         } else if a.is_smaller_than(b) {
-//                             ComparisonResult::Worse
-//                         } else {
-//                             ComparisonResult::Better
-//                         }
-//                     }
-//                     (Some((a, sa)), None) => ComparisonResult::Better,
-//                     (None, Some((a, sa))) => ComparisonResult::Worse,
-//                     (None, None) => ComparisonResult::Equivalent,
-//                 };
-//                 let res_mx: ComparisonResult = match (mxa, mxb) {
-//                     (Some((a, sa)), Some((b, sb))) => {
-//                         if a == b {
-//                             ComparisonResult::Equivalent
-//                         } else if a.is_smaller_than(b) {
-//                             ComparisonResult::Better
-//                         } else {
-//                             ComparisonResult::Worse
-//                         }
-//                     }
-//                     (Some((a, sa)), None) => ComparisonResult::Better,
-//                     (None, Some((a, sa))) => ComparisonResult::Worse,
-//                     (None, None) => ComparisonResult::Equivalent,
-//                 };
-//                 match (res_mn, res_mx) {
-//                     (ComparisonResult::Equivalent, ComparisonResult::Equivalent) => {
-//                         ComparisonResult::Equivalent
-//                     }
-//                     (
-//                         ComparisonResult::Better | ComparisonResult::Equivalent,
-//                         ComparisonResult::Better | ComparisonResult::Equivalent,
-//                     ) => ComparisonResult::Better,
-//                     (
-//                         ComparisonResult::Worse | ComparisonResult::Equivalent,
-//                         ComparisonResult::Worse | ComparisonResult::Equivalent,
-//                     ) => ComparisonResult::Worse,
-//                     (ComparisonResult::Worse, ComparisonResult::Better)
-//                     | (ComparisonResult::Better, ComparisonResult::Worse) => {
-//                         ComparisonResult::Incomparable
-//                     }
-//                     (ComparisonResult::Incomparable, _) | (_, ComparisonResult::Incomparable) => {
-//                         ComparisonResult::Incomparable
-//                     }
-//                 }
-//             }
-//             (Self::Exclusion { .. }, Self::Exclusion { .. }) => ComparisonResult::Equivalent,
-//             (Self::Exclusion { .. }, Self::Unknown) => ComparisonResult::Better,
-//             (Self::Unknown, Self::Unknown) => ComparisonResult::Equivalent,
-//             _ => {
-//                 panic!("impossible case which should have been handled by comparing and swapping before the comparison");
-//             }
-//         };
-//         if should_flip_result {
-//             res.flip()
-//         } else {
-//             res
-//         }
-//     }
-// }
+impl CpxTime {
+    pub fn num(&self) -> u32 {
+        match self {
+            CpxTime::Constant => 0,
+            CpxTime::Linear => 1,
+            CpxTime::Polynomial => 2,
+            CpxTime::Exponential => 3,
+            CpxTime::Tower => 4,
+            CpxTime::Exists => 5,
+        }
+    }
+
+    pub fn is_smaller_than(&self, other: &Self) -> bool {
+        self.num() < other.num()
+    }
+}
+
+impl SourcedCpxInfo {
+    fn num(&self) -> u32 {
+        match self {
+            Self::Equal { .. } => 0,
+            Self::Inclusion { .. } => 1,
+            Self::Exclusion { .. } => 2,
+            Self::Unknown => 3,
+        }
+    }
+
+    /// How `self` compares to `other`, relations that cannot both hold give an error.
+    pub fn compare_to(&self, other: &SourcedCpxInfo) -> Result<ComparisonResult, CombinationError> {
+        let contradiction = |a: &PartialResult, b: &PartialResult| {
+            Err(CombinationError::ExclusionInclusion(Box::new(a.clone()), Box::new(b.clone())))
+        };
+        Ok(match (self, other) {
+            (Self::Equal { .. }, Self::Equal { .. }) => ComparisonResult::Equivalent,
+            (Self::Equal { .. }, Self::Inclusion { .. }) => ComparisonResult::Better,
+            (Self::Equal { source: a }, Self::Exclusion { source: b }) => return contradiction(a, b),
+            (Self::Equal { .. }, Self::Unknown) => ComparisonResult::Better,
+            // lower bounds are weaker exclusions
+            (Self::Inclusion { mx: None, .. }, Self::Exclusion { .. }) => ComparisonResult::Worse,
+            (Self::Inclusion { mx: Some((_, a)), .. }, Self::Exclusion { source: b }) => return contradiction(a, b),
+            (Self::Inclusion { .. }, Self::Unknown) => ComparisonResult::Better,
+            (Self::Inclusion { mn: mna, mx: mxa }, Self::Inclusion { mn: mnb, mx: mxb }) => {
+                let res_mn: ComparisonResult = match (mna, mnb) {
+                    (Some((a, _)), Some((b, _))) => {
+                        if a == b {
+                            ComparisonResult::Equivalent
+                        } else if a.is_smaller_than(b) {
+                            ComparisonResult::Worse
+                        } else {
+                            ComparisonResult::Better
+                        }
+                    }
+                    (Some(_), None) => ComparisonResult::Better,
+                    (None, Some(_)) => ComparisonResult::Worse,
+                    (None, None) => ComparisonResult::Equivalent,
+                };
+                let res_mx: ComparisonResult = match (mxa, mxb) {
+                    (Some((a, _)), Some((b, _))) => {
+                        if a == b {
+                            ComparisonResult::Equivalent
+                        } else if a.is_smaller_than(b) {
+                            ComparisonResult::Better
+                        } else {
+                            ComparisonResult::Worse
+                        }
+                    }
+                    (Some(_), None) => ComparisonResult::Better,
+                    (None, Some(_)) => ComparisonResult::Worse,
+                    (None, None) => ComparisonResult::Equivalent,
+                };
+                match (res_mn, res_mx) {
+                    (ComparisonResult::Equivalent, ComparisonResult::Equivalent) => {
+                        ComparisonResult::Equivalent
+                    }
+                    (
+                        ComparisonResult::Better | ComparisonResult::Equivalent,
+                        ComparisonResult::Better | ComparisonResult::Equivalent,
+                    ) => ComparisonResult::Better,
+                    (
+                        ComparisonResult::Worse | ComparisonResult::Equivalent,
+                        ComparisonResult::Worse | ComparisonResult::Equivalent,
+                    ) => ComparisonResult::Worse,
+                    (ComparisonResult::Worse, ComparisonResult::Better)
+                    | (ComparisonResult::Better, ComparisonResult::Worse) => {
+                        ComparisonResult::Incomparable
+                    }
+                    (ComparisonResult::Incomparable, _) | (_, ComparisonResult::Incomparable) => {
+                        ComparisonResult::Incomparable
+                    }
+                }
+            }
+            (Self::Exclusion { .. }, Self::Exclusion { .. }) => ComparisonResult::Equivalent,
+            (Self::Exclusion { .. }, Self::Unknown) => ComparisonResult::Better,
+            (Self::Unknown, Self::Unknown) => ComparisonResult::Equivalent,
+            // the remaining pairs are the ones above in the other order
+            (Self::Inclusion { .. }, Self::Equal { .. })
+            | (Self::Exclusion { .. }, Self::Equal { .. } | Self::Inclusion { .. })
+            | (Self::Unknown, Self::Equal { .. } | Self::Inclusion { .. } | Self::Exclusion { .. }) => {
+                other.compare_to(self)?.flip()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/compare.rs"]
+mod tests;
@@ -0,0 +1,98 @@
+//! Consistency check of the relations, finds pairs of parameters where
+//! the known facts both claim and refute a bound.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::data::Data;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::work::combine::CombinationError;
+use crate::work::explain::{ProofStyle, ProofTree, proof_tree};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContradictionKind {
+    ExclusionInclusion,
+    IncompatibleWithEquivalence,
+    LowerAboveUpper,
+}
+
+/// Two partial results about the same pair of parameters that cannot both hold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contradiction {
+    pub subset: PreviewParameterId,
+    pub superset: PreviewParameterId,
+    pub kind: ContradictionKind,
+    pub first: usize,
+    pub second: usize,
+}
+
+impl From<CombinationError> for Contradiction {
+    fn from(err: CombinationError) -> Self {
+        let (kind, first, second) = match err {
+            CombinationError::ExclusionInclusion(a, b) => (ContradictionKind::ExclusionInclusion, a, b),
+            CombinationError::IncompatibleWithEquivalence(a, b) => {
+                (ContradictionKind::IncompatibleWithEquivalence, a, b)
+            }
+            CombinationError::LowerAboveUpper(a, b) => (ContradictionKind::LowerAboveUpper, a, b),
+        };
+        Self {
+            subset: first.relation.subset.clone(),
+            superset: first.relation.superset.clone(),
+            kind,
+            first: first.handle,
+            second: second.handle,
+        }
+    }
+}
+
+/// Keeps a single contradiction for each pair of parameters.
+pub fn collect_contradictions(errors: Vec<CombinationError>) -> Vec<Contradiction> {
+    let mut seen: HashSet<(PreviewParameterId, PreviewParameterId)> = HashSet::new();
+    errors
+        .into_iter()
+        .map(Contradiction::from)
+        .filter(|x| seen.insert((x.subset.clone(), x.superset.clone())))
+        .collect()
+}
+
+fn format_side(data: &Data, handle: usize) -> String {
+    let proof = proof_tree(data, handle);
+    let mut res = format!("{}\n\nstated facts:\n\n", proof.statement(data, ProofStyle::Plain));
+    for leaf in proof.leaves() {
+        res += &format!(
+            "- {} ({})\n",
+            leaf.statement(data, ProofStyle::Plain),
+            leaf.reason(ProofStyle::Plain)
+        );
+    }
+    res += &format!("\nderivation:\n\n{}\n", proof.format(data, ProofStyle::Plain));
+    res
+}
+
+/// Human readable report that lists the facts behind both sides of every contradiction.
+pub fn contradiction_report(data: &Data) -> String {
+    let mut res = String::from("# Contradictions\n\n");
+    if data.contradictions.is_empty() {
+        res += "No contradictions found.\n";
+        return res;
+    }
+    for contradiction in &data.contradictions {
+        let subset = data.parameters.get(&contradiction.subset);
+        let superset = data.parameters.get(&contradiction.superset);
+        res += &format!(
+            "## {} and {} ({:?})\n\n",
+            subset.map_or(contradiction.subset.to_string(), |x| x.name_core.name.clone()),
+            superset.map_or(contradiction.superset.to_string(), |x| x.name_core.name.clone()),
+            contradiction.kind
+        );
+        res += &format_side(data, contradiction.first);
+        res += &format_side(data, contradiction.second);
+    }
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/contradiction.rs"]
+mod tests;
@@ -15,6 +15,7 @@ use crate::data::preview::*;
 use crate::input::raw::{RawProvider, RawProviderLink, RawTag};
 use crate::input::raw_enums::{RawGraphClassDefinition, RawGraphClassVariant, RawOwn};
 use crate::input::source::Def;
+use crate::input::source::RawDrawing;
 use crate::input::source::RawFact;
 use crate::input::source::RawNotedSource;
 use crate::input::source::RawWrote;
@@ -83,13 +84,14 @@ impl ParameterDefinition {
                         .clone(),
                 )
             }
-            RawParameterDefinition::FromParametricParameter(preview_id) => {
+            RawParameterDefinition::FromParametricParameter(preview_id, value) => {
                 Self::FromParametricParameter(
                     preview_collection
                         .parametric_parameters_previews
                         .get(&preview_id)
                         .unwrap()
                         .clone(),
+                    value,
                 )
             }
         }
@@ -330,27 +332,27 @@ impl GraphClassVariant {
 //     }
 // }
 
-// fn str_to_preview_set(
-//     list: Vec<PreviewSetId>,
-//     preview_set_map: &HashMap<PreviewSetId, PreviewSet>,
-// ) -> Vec<PreviewSet> {
-//     let mut res = vec![];
-//     for el in list {
-//         match preview_set_map.get(&el) {
-//             Some(x) => res.push(x.clone()),
-//             None => {
-//                 error!("didn't find set with id {}", el);
-//             }
-//         }
-//     }
-//     res
-// }
-//
-// impl Drawing {
-//     pub fn from(raw: &RawDrawing, preview_set_map: &HashMap<PreviewSetId, PreviewSet>) -> Drawing {
-//         match raw {
-//             RawDrawing::Table(q) => Drawing::Table(str_to_preview_set(q.clone(), preview_set_map)),
-//             RawDrawing::Hasse(q) => Drawing::Hasse(str_to_preview_set(q.clone(), preview_set_map)),
-//         }
-//     }
-// }
+impl Drawing {
+    pub fn from(raw: RawDrawing, preview_collection: &PreviewCollection) -> Self {
+        let RawDrawing {
+            id,
+            page,
+            kind,
+            parameters,
+        } = raw;
+        let mut res = vec![];
+        for parameter in parameters {
+            match preview_collection.parameters_previews.get(&parameter) {
+                Some(x) => res.push(x.clone()),
+                None => error!("drawing {} refers to unknown parameter {}", id, parameter),
+            }
+        }
+        Self {
+            id: id.preview(),
+            page,
+            kind,
+            parameters: res,
+            known_then: vec![], // filled in by process_raw_data once the relations are inferred
+        }
+    }
+}
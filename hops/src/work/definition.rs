@@ -0,0 +1,74 @@
+//! Relations between parameters that hold by how the parameters are defined.
+
+use std::collections::HashMap;
+
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::input::raw::RawParameter;
+use crate::input::raw_enums::RawParameterDefinition;
+use crate::work::inference::WorkRelation;
+use crate::work::preview_collection::PreviewCollection;
+
+/// Relations along with the parameter whose definition gives them.
+pub type DefinedRelations = Vec<(PreviewParameter, WorkRelation, CpxInfo)>;
+
+fn upper_bound(time: CpxTime) -> CpxInfo {
+    CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(time),
+    }
+}
+
+/// An intersection upper bounds each of its parameters. A higher order parameter upper
+/// bounds its finite concretizations and is upper bounded by the concretization at
+/// infinity, this assumes the parametric parameter grows with its argument.
+pub fn definition_relations<'a>(
+    parameters: impl IntoIterator<Item = &'a RawParameter>,
+    preview_collection: &PreviewCollection,
+) -> DefinedRelations {
+    let mut res: DefinedRelations = vec![];
+    let mut higher_order: HashMap<&PreviewParametricParameterId, &PreviewParameter> = HashMap::new();
+    let mut concretizations: Vec<(&PreviewParametricParameterId, &Value, &PreviewParameter)> = vec![];
+    for parameter in parameters {
+        let Some(preview) = preview_collection.parameters_previews.get(&parameter.id.preview()) else {
+            continue;
+        };
+        let components: Vec<&PreviewParameterId> = match &parameter.definition {
+            RawParameterDefinition::IntersectionParameters(ids) => ids.iter().collect(),
+            RawParameterDefinition::IntersectionParameterGraphClass(id, _)
+            | RawParameterDefinition::IntersectionParameterProperty(id, _) => vec![id],
+            RawParameterDefinition::BoundsAll(parametric) => {
+                higher_order.insert(parametric, preview);
+                vec![]
+            }
+            RawParameterDefinition::FromParametricParameter(parametric, value) => {
+                concretizations.push((parametric, value, preview));
+                vec![]
+            }
+            _ => vec![],
+        };
+        for component in components {
+            res.push((
+                preview.clone(),
+                WorkRelation::new(&preview.id, component),
+                upper_bound(CpxTime::Linear),
+            ));
+        }
+    }
+    for (parametric, value, concretization) in concretizations {
+        let Some(higher) = higher_order.get(parametric) else {
+            continue;
+        };
+        let relation = match value {
+            Value::Infinity => WorkRelation::new(&concretization.id, &higher.id),
+            Value::Value(_) => WorkRelation::new(&higher.id, &concretization.id),
+        };
+        res.push((concretization.clone(), relation, upper_bound(CpxTime::Exists)));
+    }
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/definition.rs"]
+mod tests;
@@ -0,0 +1,271 @@
+//! Unfolds inferred relations into proof trees that end in cited sources.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::bound::Bound;
+use crate::data::data::*;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::output::to_markdown::ToMarkdown;
+use crate::work::inference::PartialResult;
+
+/// Derivation of a single partial result, premises are the results it was derived from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofTree {
+    /// Handle of the partial result, derivations are referred to by it.
+    pub handle: Option<usize>,
+    pub subset: PreviewParameterId,
+    pub superset: PreviewParameterId,
+    pub cpx: CpxInfo,
+    pub bound: Option<Bound>,
+    pub created_by: CreatedBy,
+    pub premises: Vec<ProofTree>,
+    /// The derivation was given earlier and its premises are left out.
+    #[serde(default)]
+    pub repeated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofStyle {
+    Markdown,
+    Plain,
+}
+
+/// Handles of the partial results a result was directly derived from.
+pub fn premise_handles(created_by: &CreatedBy) -> Vec<usize> {
+    match created_by {
+        CreatedBy::TransitiveInclusion(a, b)
+        | CreatedBy::TransitiveExclusion(a, b)
+        | CreatedBy::TransitiveLowerBound(a, b)
+        | CreatedBy::ParallelComposition(a, b)
+        | CreatedBy::SameThroughEquivalence(a, b) => vec![*a, *b],
+        CreatedBy::SumInclusion(handles) => handles.clone(),
+        CreatedBy::TransferredFrom(_, a) => vec![*a],
+        CreatedBy::Directly(_) | CreatedBy::ClassInclusion(..) | CreatedBy::ByDefinition(_) | CreatedBy::Todo => vec![],
+    }
+}
+
+/// Builds derivations that expand every partial result only once, a result that was already
+/// expanded by an earlier derivation of the same builder is referred to by its handle.
+#[derive(Debug, Default)]
+pub struct ProofBuilder {
+    expanded: HashSet<usize>,
+}
+
+impl ProofBuilder {
+    pub fn proof_tree(&mut self, data: &Data, handle: usize) -> ProofTree {
+        let PartialResult {
+            created_by,
+            relation,
+            cpx,
+            bound,
+            ..
+        } = &data.partial_results[handle];
+        let premises = premise_handles(created_by);
+        let repeated = !premises.is_empty() && !self.expanded.insert(handle);
+        ProofTree {
+            handle: Some(handle),
+            subset: relation.subset.clone(),
+            superset: relation.superset.clone(),
+            cpx: cpx.clone(),
+            bound: bound.clone(),
+            created_by: created_by.clone(),
+            premises: match repeated {
+                true => vec![],
+                false => premises.into_iter().map(|premise| self.proof_tree(data, premise)).collect(),
+            },
+            repeated,
+        }
+    }
+
+    /// Proofs of all the partial results that make up the relation.
+    pub fn relation_proofs(&mut self, data: &Data, cpx: &SourcedCpxInfo) -> Vec<ProofTree> {
+        relation_handles(cpx)
+            .into_iter()
+            .map(|handle| self.proof_tree(data, handle))
+            .collect()
+    }
+}
+
+/// Builds the derivation of the partial result with the given handle.
+pub fn proof_tree(data: &Data, handle: usize) -> ProofTree {
+    ProofBuilder::default().proof_tree(data, handle)
+}
+
+/// Handles of the partial results which together give the relation.
+fn relation_handles(cpx: &SourcedCpxInfo) -> Vec<usize> {
+    match cpx {
+        SourcedCpxInfo::Equal { source } | SourcedCpxInfo::Exclusion { source } => {
+            vec![source.handle]
+        }
+        SourcedCpxInfo::Inclusion { mn, mx } => {
+            let mut res: Vec<usize> = vec![];
+            for (_, source) in mn.iter().chain(mx.iter()) {
+                if !res.contains(&source.handle) {
+                    res.push(source.handle);
+                }
+            }
+            res
+        }
+        SourcedCpxInfo::Unknown => vec![],
+    }
+}
+
+/// Proofs of everything known about how `subset` relates to `superset`.
+pub fn explain_relation(
+    data: &Data,
+    subset: &PreviewParameterId,
+    superset: &PreviewParameterId,
+) -> Vec<ProofTree> {
+    let mut builder = ProofBuilder::default();
+    data.relation(subset, superset)
+        .forward
+        .into_iter()
+        .flat_map(|cpx| builder.relation_proofs(data, cpx))
+        .collect()
+}
+
+impl ProofTree {
+    fn parameter_name(data: &Data, id: &PreviewParameterId, style: ProofStyle) -> String {
+        match (style, data.parameters.get(id)) {
+            (ProofStyle::Plain, Some(parameter)) => parameter.name_core.name.clone(),
+            _ => format!("[[{}]]", id),
+        }
+    }
+
+    /// The proved relation as a sentence.
+    pub fn statement(&self, data: &Data, style: ProofStyle) -> String {
+        let a = Self::parameter_name(data, &self.subset, style);
+        let b = Self::parameter_name(data, &self.superset, style);
+        match &self.cpx {
+            CpxInfo::Equal => format!("{} is equal to {}", a, b),
+            CpxInfo::Inclusion { mn, mx } => {
+                let mut parts = vec![];
+                if let Some(mx) = mx {
+                    let mut bound = mx.to_markdown().unwrap();
+                    match (&self.bound, style) {
+                        (Some(exact), ProofStyle::Markdown) => bound += &format!(" ${}$", exact.to_latex()),
+                        (Some(exact), ProofStyle::Plain) => bound += &format!(" {}", exact),
+                        (None, _) => {}
+                    }
+                    parts.push(format!("{} upper bounds {} by {}", a, b, bound));
+                }
+                if let Some(mn) = mn {
+                    let bound = mn.to_markdown().unwrap();
+                    parts.push(format!("{} lower bounds {} by {}", a, b, bound));
+                }
+                parts.join(" and ")
+            }
+            CpxInfo::Exclusion => format!("{} does not upper bound {}", a, b),
+            CpxInfo::Unknown => format!("relation of {} and {} is unknown", a, b),
+        }
+    }
+
+    /// Why the relation holds, without the premises themselves.
+    pub fn reason(&self, style: ProofStyle) -> String {
+        match &self.created_by {
+            CreatedBy::Directly(source) => match style {
+                ProofStyle::Markdown => format!("by [[{}]]", source.id),
+                ProofStyle::Plain => {
+                    format!("by {} ({})", source.sourcekey.get_name(), source.time)
+                }
+            },
+            CreatedBy::ClassInclusion(source, showed_id) => match style {
+                ProofStyle::Markdown => {
+                    format!("by inclusion of graph classes `{}` of [[{}]]", showed_id, source.id)
+                }
+                ProofStyle::Plain => format!(
+                    "by inclusion of graph classes {} of {} ({})",
+                    showed_id,
+                    source.sourcekey.get_name(),
+                    source.time
+                ),
+            },
+            CreatedBy::ByDefinition(parameter) => match style {
+                ProofStyle::Markdown => format!("by definition of [[{}]]", parameter.id),
+                ProofStyle::Plain => format!("by definition of {}", parameter.name_core.name),
+            },
+            CreatedBy::TransitiveInclusion(_, _) => "by transitivity of the bounds".into(),
+            CreatedBy::TransitiveExclusion(_, _) => "by combining a bound with a non-bound".into(),
+            CreatedBy::TransitiveLowerBound(_, _) => "by combining a lower bound with a linear bound".into(),
+            CreatedBy::ParallelComposition(_, _) => "by combining both bounds".into(),
+            CreatedBy::SameThroughEquivalence(_, _) => "through an equivalence".into(),
+            CreatedBy::SumInclusion(_) => "by bounding every part of the intersection".into(),
+            CreatedBy::TransferredFrom(group, _) => {
+                format!("transferred by the {:?} group", group)
+            }
+            CreatedBy::Todo => "todo".into(),
+        }
+    }
+
+    /// The directly stated facts the proof relies on.
+    pub fn leaves(&self) -> Vec<&ProofTree> {
+        if self.repeated {
+            return vec![];
+        }
+        if self.premises.is_empty() {
+            return vec![self];
+        }
+        self.premises.iter().flat_map(|x| x.leaves()).collect()
+    }
+
+    fn referred(&self, res: &mut HashSet<usize>) {
+        if self.repeated {
+            res.extend(self.handle);
+        }
+        for premise in &self.premises {
+            premise.referred(res);
+        }
+    }
+
+    /// Renders the tree as a nested list, one derivation step per line.
+    pub fn format(&self, data: &Data, style: ProofStyle) -> String {
+        Self::format_all(std::slice::from_ref(self), data, style).remove(0)
+    }
+
+    /// Renders each of the trees as a nested list. A derivation that was given earlier is
+    /// referred to by its number, which is shown where the derivation is given.
+    pub fn format_all(proofs: &[ProofTree], data: &Data, style: ProofStyle) -> Vec<String> {
+        let mut referred = HashSet::new();
+        for proof in proofs {
+            proof.referred(&mut referred);
+        }
+        proofs
+            .iter()
+            .map(|proof| {
+                let mut res = String::new();
+                proof.format_rec(data, style, &referred, 0, &mut res);
+                res
+            })
+            .collect()
+    }
+
+    fn format_rec(&self, data: &Data, style: ProofStyle, referred: &HashSet<usize>, depth: usize, res: &mut String) {
+        let bullet = match style {
+            ProofStyle::Markdown => "* ",
+            ProofStyle::Plain => "- ",
+        };
+        let reason = match self.handle {
+            Some(handle) if self.repeated => format!("see derivation #{}", handle),
+            Some(handle) if referred.contains(&handle) => format!("{}, derivation #{}", self.reason(style), handle),
+            _ => self.reason(style),
+        };
+        *res += &format!(
+            "{}{}{} ({})\n",
+            "  ".repeat(depth),
+            bullet,
+            self.statement(data, style),
+            reason
+        );
+        for premise in &self.premises {
+            premise.format_rec(data, style, referred, depth + 1, res);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/explain.rs"]
+mod tests;
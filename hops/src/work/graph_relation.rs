@@ -0,0 +1,187 @@
+//! Order of graph relations by their operations and inclusions of graph classes under them.
+//!
+//! Relation r implies relation s if every operation of r is also an operation of s.
+//! If the graphs of A are r-contained in B then they are also s-contained in B.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::data::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::input::source::{ImplicationRelation, InclusionRelationUnderGraphRelation};
+
+pub type GcGcArcs = Vec<(PreviewGraphClass, PreviewGraphClass, InclusionRelationUnderGraphRelation)>;
+
+/// Reflexive and transitive implication order on graph relations.
+#[derive(Debug, Default)]
+pub struct GraphRelationOrder {
+    implied: HashMap<PreviewGraphRelationId, HashSet<PreviewGraphRelationId>>,
+}
+
+impl GraphRelationOrder {
+    /// Compares the operation sets, relations defined only by text relate to nothing but themselves.
+    pub fn new(graph_relations: &[GraphRelation]) -> Self {
+        let operations: Vec<(PreviewGraphRelationId, Option<HashSet<PreviewOperationId>>)> = graph_relations
+            .iter()
+            .map(|relation| {
+                let ops = match &relation.displayed_definition {
+                    GraphRelationDefinition::IsomorphicAfterOperations(ops) => {
+                        Some(ops.iter().map(|op| op.id.clone()).collect())
+                    }
+                    GraphRelationDefinition::Text(_) => None,
+                };
+                (relation.id.preview(), ops)
+            })
+            .collect();
+        let mut implied: HashMap<PreviewGraphRelationId, HashSet<PreviewGraphRelationId>> = HashMap::new();
+        for (a, a_ops) in &operations {
+            let entry = implied.entry(a.clone()).or_default();
+            entry.insert(a.clone());
+            let Some(a_ops) = a_ops else {
+                continue;
+            };
+            for (b, b_ops) in &operations {
+                if b_ops.as_ref().is_some_and(|b_ops| a_ops.is_subset(b_ops)) {
+                    entry.insert(b.clone());
+                }
+            }
+        }
+        Self { implied }
+    }
+
+    /// Inclusion under `a` gives inclusion under `b`.
+    pub fn implies(&self, a: &PreviewGraphRelationId, b: &PreviewGraphRelationId) -> bool {
+        a == b || self.implied.get(a).is_some_and(|x| x.contains(b))
+    }
+
+    /// Relations other than `relation` that a class closed under `relation` is also closed under.
+    /// Every relation that implies `relation` applies only operations of `relation`.
+    pub fn also_closed_under(&self, relation: &PreviewGraphRelationId) -> Vec<PreviewGraphRelationId> {
+        let mut res: Vec<PreviewGraphRelationId> = self
+            .implied
+            .keys()
+            .filter(|x| *x != relation && self.implies(x, relation))
+            .cloned()
+            .collect();
+        res.sort_by_key(|x| x.to_string());
+        res
+    }
+
+    /// Pairs of distinct relations where the first implies the second.
+    pub fn pairs(&self) -> Vec<(PreviewGraphRelationId, PreviewGraphRelationId)> {
+        let mut res: Vec<(PreviewGraphRelationId, PreviewGraphRelationId)> = self
+            .implied
+            .iter()
+            .flat_map(|(a, bs)| bs.iter().filter(move |b| *b != a).map(move |b| (a.clone(), b.clone())))
+            .collect();
+        res.sort_by_key(|(a, b)| (a.to_string(), b.to_string()));
+        res
+    }
+
+    /// The weakest relations implied by both `a` and `b`.
+    fn joins(&self, a: &PreviewGraphRelationId, b: &PreviewGraphRelationId) -> Vec<PreviewGraphRelationId> {
+        let candidates: Vec<&PreviewGraphRelationId> = self
+            .implied
+            .get(a)
+            .into_iter()
+            .flatten()
+            .filter(|x| self.implies(b, x))
+            .collect();
+        candidates
+            .iter()
+            .filter(|x| {
+                !candidates
+                    .iter()
+                    .any(|y| self.implies(y, x) && !self.implies(x, y))
+            })
+            .map(|x| (*x).clone())
+            .collect()
+    }
+}
+
+/// Adds `relation` to the relations known for a pair unless a known one already implies it.
+fn insert_weakest(
+    known: &mut Vec<PreviewGraphRelationId>,
+    relation: PreviewGraphRelationId,
+    order: &GraphRelationOrder,
+) -> bool {
+    if known.iter().any(|x| order.implies(x, &relation)) {
+        return false;
+    }
+    known.retain(|x| !order.implies(&relation, x));
+    known.push(relation);
+    true
+}
+
+/// Closes the inclusions of graph classes under transitivity. Chaining inclusions under
+/// two relations gives an inclusion under the weakest relations implied by both.
+pub fn close_gc_gc(arcs: GcGcArcs, order: &GraphRelationOrder) -> GcGcArcs {
+    let mut classes: HashMap<PreviewGraphClassId, PreviewGraphClass> = HashMap::new();
+    let mut known: HashMap<(PreviewGraphClassId, PreviewGraphClassId), Vec<PreviewGraphRelationId>> =
+        HashMap::new();
+    for (a, b, inclusion) in &arcs {
+        classes.insert(a.id.clone(), a.clone());
+        classes.insert(b.id.clone(), b.clone());
+        let pairs = match inclusion.relation {
+            ImplicationRelation::Implies => vec![(a, b)],
+            ImplicationRelation::Equivalent => vec![(a, b), (b, a)],
+            ImplicationRelation::Excludes | ImplicationRelation::Unknown => vec![],
+        };
+        for (x, y) in pairs {
+            let entry = known.entry((x.id.clone(), y.id.clone())).or_default();
+            insert_weakest(entry, inclusion.graph_relation.clone(), order);
+        }
+    }
+    let stated = known.clone();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let snapshot: Vec<((PreviewGraphClassId, PreviewGraphClassId), Vec<PreviewGraphRelationId>)> =
+            known.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        for ((a, b), first) in &snapshot {
+            for ((c, d), second) in &snapshot {
+                if b != c || a == d {
+                    continue;
+                }
+                for r in first {
+                    for s in second {
+                        for t in order.joins(r, s) {
+                            let entry = known.entry((a.clone(), d.clone())).or_default();
+                            changed |= insert_weakest(entry, t, order);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let mut res = arcs;
+    let mut derived: Vec<(PreviewGraphClass, PreviewGraphClass, InclusionRelationUnderGraphRelation)> = known
+        .into_iter()
+        .flat_map(|((a, b), relations)| {
+            let stated = stated.get(&(a.clone(), b.clone())).cloned().unwrap_or_default();
+            let (a, b) = (classes[&a].clone(), classes[&b].clone());
+            relations
+                .into_iter()
+                .filter(move |r| !stated.contains(r))
+                .map(move |graph_relation| {
+                    (
+                        a.clone(),
+                        b.clone(),
+                        InclusionRelationUnderGraphRelation {
+                            relation: ImplicationRelation::Implies,
+                            graph_relation,
+                        },
+                    )
+                })
+        })
+        .collect();
+    derived.sort_by_key(|(a, b, inclusion)| {
+        (a.id.to_string(), b.id.to_string(), inclusion.graph_relation.to_string())
+    });
+    res.extend(derived);
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/graph_relation.rs"]
+mod tests;
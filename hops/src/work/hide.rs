@@ -1,104 +1,255 @@
 //! Given a set of relations find out the essential subset from which
 //! the other relations can be implied.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::data::data::Data;
-// use crate::work::combine::combine_serial;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::score::{Score, has_better_score_than};
+use crate::work::combine::combine_serial;
+use crate::work::explain::{ProofBuilder, ProofStyle, ProofTree, premise_handles, proof_tree};
+use crate::work::inference::PartialResult;
 
-// fn rel_can_be_implied_through(
-//     map: &HashMap<WorkRelation, PreviewRelation>,
-//     relation: &PreviewRelation,
-//     midset: &PreviewSet,
-// ) -> bool {
-//     if let Some(mx) = relation.cpx.get_mx() {
-//         assert_ne!(relation.subset, relation.superset);
-//         assert_ne!(midset, &relation.superset);
-//         assert_ne!(midset, &relation.subset);
-//         let upper_work = WorkRelation::new(&relation.subset.id, &midset.id);
-//         let lower_work = WorkRelation::new(&midset.id, &relation.superset.id);
-//         if let (Some(upper_relation), Some(lower_relation)) =
-//             (map.get(&upper_work), map.get(&lower_work))
-//         {
-//             let mock_a = PartialResult {
-//                 handle: 0,
-//                 created_by: crate::data::enums::CreatedBy::Todo,
-//                 cpx: CpxInfo::Unknown,
-//                 relation: upper_work.clone(),
-//             };
-//             let mock_b = PartialResult {
-//                 handle: 0,
-//                 created_by: crate::data::enums::CreatedBy::Todo,
-//                 cpx: CpxInfo::Unknown,
-//                 relation: lower_work.clone(),
-//             };
-//             // todo fix
-//             if let (Some(a), Some(b)) = (upper_relation.cpx.get_mx(), lower_relation.cpx.get_mx()) {
-//                 let (c, d, e) = combine_serial((a, mock_a.clone()), (b, mock_b.clone()));
-//                 if !mx.is_smaller_than(&e) {
-//                     return true;
-//                 }
-//             }
-//         }
-//     }
-//     false
-// }
+/// Stated fact that also follows from the other facts.
+#[derive(Debug, Clone)]
+pub struct Redundancy {
+    pub fact: usize,
+    pub alternative: ProofTree,
+    /// The alternative gives a strictly better bound than the fact.
+    pub weaker_than_known: bool,
+}
 
-// fn could_be_hidden(
-//     map: &HashMap<WorkRelation, PreviewRelation>,
-//     relation: &PreviewRelation,
-//     with_respect_to: &Vec<PreviewSet>,
-// ) -> bool {
-//     if relation.superset == relation.subset {
-//         return true;
-//     }
-//     if let Some(mx) = &relation.cpx.get_mx() {
-//         for midset in with_respect_to {
-//             if midset == &relation.superset || midset == &relation.subset {
-//                 continue;
-//             }
-//             // cannot use this midset to hide the relation if it does not imply it
-//             if !rel_can_be_implied_through(map, relation, midset) {
-//                 continue;
-//             }
-//             // even if connection through mid implies the relation we still need
-//             // to prevent mutual hiding for sets that bound each other
-//             // case 1 -- subset and midset are mutually bounded
-//             if let Some(same) = map.get(&WorkRelation::new(&midset.id, &relation.superset.id))
-//                 && rel_can_be_implied_through(map, same, &relation.subset)
-//                 && relation.subset.has_better_score_than(midset)
-//             {
-//                 continue;
-//             }
-//             // case 2 -- superset and midset are mutually bounded
-//             if let Some(same) = map.get(&WorkRelation::new(&relation.subset.id, &midset.id))
-//                 && rel_can_be_implied_through(map, same, &relation.superset)
-//                 && relation.superset.has_better_score_than(midset)
-//             {
-//                 continue;
-//             }
-//             return true;
-//         }
-//     }
-//     false
-// }
+fn upper_bound(cpx: &SourcedCpxInfo) -> Option<(CpxTime, &PartialResult)> {
+    match cpx {
+        SourcedCpxInfo::Equal { source } => Some((CpxTime::Linear, source)),
+        SourcedCpxInfo::Inclusion {
+            mx: Some((time, source)),
+            ..
+        } => Some((time.clone(), source)),
+        _ => None,
+    }
+}
 
-// pub fn filter_hidden(
-//     potential_relations: Vec<PreviewRelation>,
-//     displayed_sets: &Vec<PreviewSet>,
-// ) -> Vec<PreviewRelation> {
-//     let mut map: HashMap<WorkRelation, PreviewRelation> = HashMap::new();
-//     for pr in &potential_relations {
-//         map.insert(
-//             WorkRelation::new(&pr.subset.id, &pr.superset.id),
-//             pr.clone(),
-//         );
-//     }
-//     let mut drawn_relations = Vec::new();
-//     for relation in &potential_relations {
-//         if relation.cpx.get_mx().is_some() && !could_be_hidden(&map, relation, displayed_sets) {
-//             drawn_relations.push(relation.clone());
-//         }
-//     }
-//     drawn_relations
-// }
+fn lower_bound(cpx: &SourcedCpxInfo) -> Option<(CpxTime, &PartialResult)> {
+    match cpx {
+        SourcedCpxInfo::Inclusion {
+            mn: Some((time, source)),
+            ..
+        } => Some((time.clone(), source)),
+        _ => None,
+    }
+}
+
+/// Whether the derivation of the partial result `handle` relies on any of the `removed` facts,
+/// `memo` caches the answers for a single set of `removed` facts.
+fn relies_on(data: &Data, handle: usize, removed: &HashSet<usize>, memo: &mut HashMap<usize, bool>) -> bool {
+    if removed.contains(&handle) {
+        return true;
+    }
+    if let Some(res) = memo.get(&handle) {
+        return *res;
+    }
+    let res = premise_handles(&data.partial_results[handle].created_by)
+        .into_iter()
+        .any(|premise| relies_on(data, premise, removed, memo));
+    memo.insert(handle, res);
+    res
+}
+
+/// Derivation of the upper bound from `subset` to `superset` that does not use the `removed`
+/// facts, either through the best known bound or by composing bounds through another parameter.
+fn alternative_bound(data: &Data, fact: &PartialResult, removed: &HashSet<usize>) -> Option<(CpxTime, ProofTree)> {
+    let (subset, superset) = (&fact.relation.subset, &fact.relation.superset);
+    let mut res: Option<(CpxTime, ProofTree)> = None;
+    let mut memo: HashMap<usize, bool> = HashMap::new();
+    let mut consider = |time: CpxTime, proof: &dyn Fn() -> ProofTree| {
+        if res.as_ref().is_none_or(|(best, _)| time.is_smaller_than(best)) {
+            res = Some((time, proof()));
+        }
+    };
+    let arc = |a: &PreviewParameterId, b: &PreviewParameterId| {
+        data.relation(a, b).forward.into_iter().next().and_then(upper_bound)
+    };
+    if let Some((time, source)) = arc(subset, superset)
+        && !relies_on(data, source.handle, removed, &mut memo)
+    {
+        consider(time, &|| proof_tree(data, source.handle));
+    }
+    for midset in data.parameters.keys() {
+        if midset == subset || midset == superset {
+            continue;
+        }
+        let (Some(first), Some(second)) = (
+            arc(subset, midset),
+            arc(midset, superset),
+        ) else {
+            continue;
+        };
+        if relies_on(data, first.1.handle, removed, &mut memo)
+            || relies_on(data, second.1.handle, removed, &mut memo)
+        {
+            continue;
+        }
+        let (a, b, time) = combine_serial((first.0, first.1.clone()), (second.0, second.1.clone()));
+        let cpx = CpxInfo::Inclusion {
+            mn: None,
+            mx: Some(time.clone()),
+        };
+        let bound = match (&a.bound, &b.bound) {
+            (Some(inner), Some(outer)) => Some(outer.compose(inner)),
+            _ => None,
+        };
+        consider(time, &|| {
+            let mut builder = ProofBuilder::default();
+            ProofTree {
+                handle: None,
+                subset: subset.clone(),
+                superset: superset.clone(),
+                cpx: cpx.clone(),
+                bound: bound.clone(),
+                created_by: CreatedBy::TransitiveInclusion(a.handle, b.handle),
+                premises: vec![builder.proof_tree(data, a.handle), builder.proof_tree(data, b.handle)],
+                repeated: false,
+            }
+        });
+    }
+    res
+}
+
+/// Lower bound from `subset` to `superset` known without the `removed` facts.
+fn alternative_lower_bound(data: &Data, fact: &PartialResult, removed: &HashSet<usize>) -> Option<CpxTime> {
+    let cpx = data.relation(&fact.relation.subset, &fact.relation.superset).forward.into_iter().next()?;
+    let (time, source) = lower_bound(cpx)?;
+    (!relies_on(data, source.handle, removed, &mut HashMap::new())).then_some(time)
+}
+
+/// Bounds stated between parameters that are implied by the other facts with an equal
+/// or better upper bound and, if the fact has one, an equal or better lower bound. A reported fact is dropped before checking the next one, so all the
+/// reported facts can be removed together.
+pub fn redundant_facts(data: &Data) -> Vec<Redundancy> {
+    let mut res = vec![];
+    let mut removed: HashSet<usize> = HashSet::new();
+    for fact in &data.partial_results {
+        // bounds derived from graph class facts are not created directly
+        let (CreatedBy::Directly(_), CpxInfo::Inclusion { mn, mx: Some(time) }) = (&fact.created_by, &fact.cpx)
+        else {
+            continue;
+        };
+        if fact.relation.subset == fact.relation.superset {
+            continue;
+        }
+        removed.insert(fact.handle);
+        let lower_implied = mn.as_ref().is_none_or(|mn| {
+            alternative_lower_bound(data, fact, &removed).is_some_and(|alternative| !alternative.is_smaller_than(mn))
+        });
+        match alternative_bound(data, fact, &removed) {
+            Some((alternative_time, alternative)) if lower_implied && !time.is_smaller_than(&alternative_time) => {
+                res.push(Redundancy {
+                    fact: fact.handle,
+                    alternative,
+                    weaker_than_known: alternative_time.is_smaller_than(time),
+                });
+            }
+            _ => {
+                removed.remove(&fact.handle);
+            }
+        }
+    }
+    res
+}
+
+/// Human readable list of the redundant facts along with their alternative derivations.
+pub fn redundancy_report(data: &Data) -> String {
+    let redundancies = redundant_facts(data);
+    let mut res = String::from("# Redundant facts\n\n");
+    if redundancies.is_empty() {
+        res += "No stated fact is implied by the others.\n";
+        return res;
+    }
+    res += &format!("{} stated facts are implied by the others.\n\n", redundancies.len());
+    let mut entries: Vec<(String, &Redundancy)> = redundancies
+        .iter()
+        .map(|x| (proof_tree(data, x.fact).statement(data, ProofStyle::Plain), x))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    for (statement, redundancy) in entries {
+        let fact = proof_tree(data, redundancy.fact);
+        res += &format!("## {} ({})\n\n", statement, fact.reason(ProofStyle::Plain));
+        if redundancy.weaker_than_known {
+            res += "The stated bound is weaker than the known one.\n\n";
+        }
+        res += &format!(
+            "alternative derivation:\n\n{}\n",
+            redundancy.alternative.format(data, ProofStyle::Plain)
+        );
+    }
+    res
+}
+
+/// Hasse diagram of the displayed items. Mutually bounded items are drawn once, as the one
+/// with the best score, and only edges that are not implied by a path through other
+/// displayed items remain. Edges go from the bounding item to the bounded one.
+pub fn filter_hidden<'a, T>(items: &[&'a T], edges: &[(String, String)]) -> (Vec<&'a T>, Vec<(String, String)>)
+where
+    T: Score + HasId,
+{
+    let index: HashMap<String, usize> = items.iter().enumerate().map(|(i, x)| (x.id(), i)).collect();
+    let n = items.len();
+    let mut reach = vec![vec![false; n]; n];
+    for (i, row) in reach.iter_mut().enumerate() {
+        row[i] = true;
+    }
+    for (from, to) in edges {
+        if let (Some(&a), Some(&b)) = (index.get(from), index.get(to)) {
+            reach[a][b] = true;
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            if !reach[i][k] {
+                continue;
+            }
+            let through = reach[k].clone();
+            for (j, reachable) in through.into_iter().enumerate() {
+                if reachable {
+                    reach[i][j] = true;
+                }
+            }
+        }
+    }
+    // every item is represented by the best scored item it is equivalent to
+    let representative: Vec<usize> = (0..n)
+        .map(|i| {
+            (0..n)
+                .filter(|&j| reach[i][j] && reach[j][i])
+                .fold(i, |best, j| {
+                    if has_better_score_than(items[j], items[best]) {
+                        j
+                    } else {
+                        best
+                    }
+                })
+        })
+        .collect();
+    let shown: Vec<usize> = (0..n).filter(|&i| representative[i] == i).collect();
+    let mut drawn = vec![];
+    for &a in &shown {
+        for &b in &shown {
+            if a == b || !reach[a][b] {
+                continue;
+            }
+            let implied = shown
+                .iter()
+                .any(|&c| c != a && c != b && reach[a][c] && reach[c][b]);
+            if !implied {
+                drawn.push((items[a].id(), items[b].id()));
+            }
+        }
+    }
+    (shown.into_iter().map(|i| items[i]).collect(), drawn)
+}
+
+#[cfg(test)]
+#[path = "tests/hide.rs"]
+mod tests;
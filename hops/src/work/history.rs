@@ -0,0 +1,78 @@
+//! Reconstructs what was known about parameters at the time a source was written, so that
+//! its figures can be compared with the current results.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::data::{Source, Wrote};
+use crate::data::date::Date;
+use crate::data::enums::*;
+use crate::data::id::{PreviewParameterId, PreviewSourceId};
+use crate::data::preview::PreviewParameter;
+use crate::work::incremental::{Fingerprint, fingerprint};
+
+/// Whether the first parameter upper bounds the second one, see `Drawing::known_then`.
+pub type KnownBounds = Vec<(PreviewParameterId, PreviewParameterId, bool)>;
+
+fn known_at(sources: &HashMap<PreviewSourceId, Source>, source_id: &PreviewSourceId, date: &Date) -> bool {
+    sources.get(source_id).is_some_and(|x| &x.time <= date)
+}
+
+/// Facts of the sources that are not newer than `date`. Sources without a year hold
+/// folklore and assumptions, so they are considered known at any time.
+pub fn factoids_until(
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    sources: &HashMap<PreviewSourceId, Source>,
+    date: &Date,
+) -> Vec<(PreviewSourceId, Vec<Wrote>)> {
+    factoids
+        .iter()
+        .filter(|(source_id, _)| known_at(sources, source_id, date))
+        .cloned()
+        .collect()
+}
+
+/// Fingerprint of everything the bounds between `drawn` known at `date` depend on, that is
+/// the structure of the closure and the sources that are not newer than `date`.
+pub fn history_fingerprint(
+    structure: Fingerprint,
+    source_fingerprints: &[(PreviewSourceId, Fingerprint)],
+    sources: &HashMap<PreviewSourceId, Source>,
+    date: &Date,
+    drawn: &[PreviewParameter],
+) -> Fingerprint {
+    let until: Vec<&(PreviewSourceId, Fingerprint)> = source_fingerprints
+        .iter()
+        .filter(|(source_id, _)| known_at(sources, source_id, date))
+        .collect();
+    fingerprint(&(structure, until, drawn))
+}
+
+/// Whether a parameter upper bounds another one for every ordered pair of distinct `parameters`
+/// whose relation is known in `arcs`.
+pub fn known_bounds(
+    parameters: &[PreviewParameter],
+    arcs: &[(PreviewParameter, PreviewParameter, SourcedCpxInfo)],
+) -> KnownBounds {
+    let drawn: HashSet<&PreviewParameterId> = parameters.iter().map(|x| &x.id).collect();
+    let mut res: KnownBounds = arcs
+        .iter()
+        .filter(|(a, b, _)| a.id != b.id && drawn.contains(&a.id) && drawn.contains(&b.id))
+        .filter_map(|(a, b, cpx)| Some((a.id.clone(), b.id.clone(), CpxInfo::from(cpx.clone()).is_bounded()?)))
+        .collect();
+    res.sort_by_key(|(a, b, _)| (a.to_string(), b.to_string()));
+    res
+}
+
+/// The bounds of `known` between the `parameters` of a single figure.
+pub fn drawing_bounds(known: &KnownBounds, parameters: &[PreviewParameter]) -> KnownBounds {
+    let drawn: HashSet<&PreviewParameterId> = parameters.iter().map(|x| &x.id).collect();
+    known
+        .iter()
+        .filter(|(a, b, _)| drawn.contains(a) && drawn.contains(b))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/history.rs"]
+mod tests;
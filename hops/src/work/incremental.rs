@@ -0,0 +1,195 @@
+//! Reuse of the relations processed in a previous run when only some sources changed.
+//!
+//! Every source is fingerprinted by its facts. Partial results that rely on a source whose
+//! facts changed are dropped and the closures continue from the rest, so only the relations
+//! affected by the change are derived again. A closure with contradictions keeps relations
+//! depending on the order of the facts, so it is derived in full. The relations known at the
+//! date of a figure are kept until one of the sources up to that date changes.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::data::data::*;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::work::definition::DefinedRelations;
+use crate::work::explain::premise_handles;
+use crate::work::history::KnownBounds;
+use crate::work::inference::{ClosureState, PartialResult, WorkRelation};
+use crate::work::transfer::ClassDistances;
+
+pub type Fingerprint = u64;
+
+pub fn fingerprint<T: Serialize>(value: &T) -> Fingerprint {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(value).unwrap_or_default().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Fingerprint of the facts of each source along with how the source is displayed.
+pub fn source_fingerprints(
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    sources: &HashMap<PreviewSourceId, Source>,
+) -> Vec<(PreviewSourceId, Fingerprint)> {
+    factoids
+        .iter()
+        .map(|(id, wrotes)| {
+            let preview = sources.get(id).map(|source| source.preview());
+            (id.clone(), fingerprint(&(preview, wrotes)))
+        })
+        .collect()
+}
+
+/// Fingerprints of the items regardless of their order.
+fn unordered<T: Serialize>(items: impl IntoIterator<Item = T>) -> Vec<Fingerprint> {
+    let mut res: Vec<Fingerprint> = items.into_iter().map(|x| fingerprint(&x)).collect();
+    res.sort();
+    res
+}
+
+/// Fingerprint of everything except the facts of sources that the closure depends on.
+/// Collections that come from hash maps are compared as sets.
+pub fn structure_fingerprint(
+    parameters: &[PreviewParameter],
+    composed_sets: &[(PreviewParameterId, Vec<PreviewParameter>)],
+    transfers: &HashMap<TransferGroup, HashMap<PreviewParameterId, Vec<PreviewParameter>>>,
+    class_distances: &ClassDistances,
+    definitions: &DefinedRelations,
+) -> Fingerprint {
+    let composed_sets = unordered(composed_sets.iter().map(|(id, set)| (id, unordered(set))));
+    let transfers = unordered(transfers.iter().flat_map(|(group, map)| {
+        map.iter().map(move |(id, to)| (format!("{:?}", group), id, unordered(to)))
+    }));
+    let class_distances = (
+        unordered(class_distances.distances.iter().map(|(id, to)| (id, unordered(to)))),
+        unordered(&class_distances.plain),
+    );
+    fingerprint(&(
+        unordered(parameters),
+        composed_sets,
+        transfers,
+        class_distances,
+        unordered(definitions),
+    ))
+}
+
+/// Closures of a previous run with the fingerprints of the input they were computed from.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProcessedRelations {
+    pub structure: Fingerprint,
+    pub sources: Vec<(PreviewSourceId, Fingerprint)>,
+    /// Closure of the facts at least as established as each state, the closure of all the
+    /// facts is the assumed one.
+    pub closures: Vec<(KnowledgeState, ClosureState)>,
+    /// Bounds between the parameters of the figures known at the dates of the figures,
+    /// keyed by `history_fingerprint`.
+    pub history: Vec<(Fingerprint, KnownBounds)>,
+}
+
+/// The part of a previous closure that is still valid.
+#[derive(Debug)]
+pub struct ReusedClosure {
+    pub partial_results: Vec<PartialResult>,
+    pub relations: HashMap<WorkRelation, PartialResult>,
+    /// Handles of the stated facts and definitions that are still valid.
+    pub seeds: Vec<usize>,
+    /// Sources whose facts need not be stated again.
+    pub unchanged: HashSet<PreviewSourceId>,
+    /// Whether the relation of some pair was dropped, the transfers and sums of the kept
+    /// relations then have to be applied again.
+    pub dropped: bool,
+}
+
+impl ProcessedRelations {
+    /// The valid part of every closure, nothing if anything else than the facts of sources
+    /// changed.
+    pub fn reuse(
+        self,
+        structure: Fingerprint,
+        sources: &[(PreviewSourceId, Fingerprint)],
+    ) -> HashMap<KnowledgeState, ReusedClosure> {
+        if self.structure != structure {
+            info!("parameter definitions changed, processing all relations");
+            return HashMap::new();
+        }
+        let current: HashMap<&PreviewSourceId, &Fingerprint> = sources.iter().map(|(id, f)| (id, f)).collect();
+        let unchanged: HashSet<PreviewSourceId> = self
+            .sources
+            .iter()
+            .filter(|(id, f)| current.get(id) == Some(&f))
+            .map(|(id, _)| id.clone())
+            .collect();
+        info!(
+            "reusing relations, {} of {} sources changed",
+            sources.len() - unchanged.len(),
+            sources.len()
+        );
+        self.closures
+            .into_iter()
+            .filter_map(|(state, closure)| Some((state, closure.reuse(&unchanged)?)))
+            .collect()
+    }
+}
+
+impl ClosureState {
+    /// Drops the partial results that rely on sources that are not `unchanged`. Gives nothing
+    /// if the closure had contradictions or if most of the partial results are no longer used,
+    /// which keeps the number of partial results carried between runs bounded.
+    fn reuse(self, unchanged: &HashSet<PreviewSourceId>) -> Option<ReusedClosure> {
+        let ClosureState {
+            partial_results,
+            seeds,
+            best,
+            contradicted,
+        } = self;
+        if contradicted {
+            return None;
+        }
+        let seeds: HashSet<usize> = seeds.into_iter().collect();
+        let mut stale = vec![false; partial_results.len()];
+        for result in &partial_results {
+            stale[result.handle] = match &result.created_by {
+                CreatedBy::Directly(source) | CreatedBy::ClassInclusion(source, _) => {
+                    !seeds.contains(&result.handle) || !unchanged.contains(&source.id)
+                }
+                CreatedBy::ByDefinition(_) => !seeds.contains(&result.handle),
+                created_by => premise_handles(created_by).iter().any(|&x| stale[x]),
+            };
+        }
+        let mut live = vec![false; partial_results.len()];
+        let mut stack: Vec<usize> = best.iter().chain(&seeds).copied().filter(|&x| !stale[x]).collect();
+        while let Some(handle) = stack.pop() {
+            if !live[handle] {
+                live[handle] = true;
+                stack.extend(premise_handles(&partial_results[handle].created_by));
+            }
+        }
+        if 2 * live.iter().filter(|&&x| x).count() < partial_results.len() {
+            info!("most of the partial results are unused, processing all relations");
+            return None;
+        }
+        let dropped = best.iter().any(|&handle| stale[handle]);
+        let relations = best
+            .into_iter()
+            .filter(|&handle| !stale[handle])
+            .map(|handle| (partial_results[handle].relation.clone(), partial_results[handle].clone()))
+            .collect();
+        let mut seeds: Vec<usize> = seeds.into_iter().filter(|&handle| !stale[handle]).collect();
+        seeds.sort();
+        Some(ReusedClosure {
+            partial_results,
+            relations,
+            seeds,
+            unchanged: unchanged.clone(),
+            dropped,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/incremental.rs"]
+mod tests;
@@ -1,3 +1,23 @@
+//! Infers relations between parameters from the relations stated in sources.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use log::{debug, info, trace};
+use serde::{Deserialize, Serialize};
+
+use crate::data::bound::Bound;
+use crate::data::data::*;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::input::source::{Cpx, ImplicationRelation, InclusionRelationUnderGraphRelation};
+use crate::work::closure::{Derivation, Interner, derivations};
+use crate::work::combine::{CombinationError, combine_serial};
+use crate::work::definition::DefinedRelations;
+use crate::work::incremental::ReusedClosure;
+use crate::work::preview_collection::PreviewCollection;
+use crate::work::transfer::{ClassDistances, apply_transfers};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WorkRelation {
     pub subset: PreviewParameterId,
@@ -15,23 +35,51 @@ pub struct PartialResult {
     pub created_by: CreatedBy,
     pub relation: WorkRelation,
     pub cpx: CpxInfo,
+    /// Exact upper bound if it is known, it is never worse than the upper bound of `cpx`.
+    #[serde(default)]
+    pub bound: Option<Bound>,
+}
+
+pub type ParameterArcs = Vec<(PreviewParameter, PreviewParameter, SourcedCpxInfo)>;
+
+/// Relations stated by a single fact along with its exact bound.
+pub type FactRelations<'a> = (Vec<(WorkRelation, CpxInfo)>, Option<&'a Bound>);
+
+/// Everything needed to continue the closure in a later run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ClosureState {
+    pub partial_results: Vec<PartialResult>,
+    /// Handles of the stated facts and definitions the closure started from.
+    pub seeds: Vec<usize>,
+    /// Handles of the best partial result of every pair.
+    pub best: Vec<usize>,
+    /// Whether some results contradicted each other, which of them were kept then depends
+    /// on the order in which they were found and the closure is not continued.
+    pub contradicted: bool,
 }
 
 fn add_and_update(
     result: PartialResult,
     relation_map: &mut HashMap<WorkRelation, PartialResult>,
     updated_relations: &mut VecDeque<WorkRelation>,
+    contradictions: &mut Vec<CombinationError>,
     partial_result_builder: &mut PartialResultsBuilder,
 ) {
     let res = if let Some(x) = relation_map.get_mut(&result.relation) {
-        if let Some(res) = x.combine_parallel(&result, partial_result_builder) {
-            trace!(
-                "updated relation (replace) {} {}",
-                x.relation.subset, x.relation.superset,
-            );
-            res
-        } else {
-            return;
+        match x.combine_parallel(&result, partial_result_builder) {
+            Ok(Some(res)) => {
+                trace!(
+                    "updated relation (replace) {} {}",
+                    x.relation.subset, x.relation.superset,
+                );
+                res
+            }
+            Ok(None) => return,
+            Err(err) => {
+                debug!("{}", err);
+                contradictions.push(err);
+                return;
+            }
         }
     } else {
         trace!(
@@ -44,313 +92,457 @@ fn add_and_update(
     relation_map.insert(res.relation.clone(), res);
 }
 
-fn process_relations(
-    composed_sets: &Vec<(PreviewParameterId, Vec<PreviewParameter>)>,
+/// Inclusion of graph classes reversed onto the distances to them, the distance
+/// to the subclass upper bounds the distance to the superclass. Inclusions under
+/// other than plain graph relations say nothing about the distances.
+fn class_distance_relations(
+    subset: &PreviewGraphClass,
+    superset: &PreviewGraphClass,
+    inclusion: &InclusionRelationUnderGraphRelation,
+    class_distances: &ClassDistances,
+) -> Vec<(WorkRelation, CpxInfo)> {
+    if !class_distances.plain.contains(&inclusion.graph_relation) {
+        return vec![];
+    }
+    let (Some(from), Some(to)) = (
+        class_distances.distances.get(&subset.id),
+        class_distances.distances.get(&superset.id),
+    ) else {
+        return vec![];
+    };
+    let cpx = match inclusion.relation {
+        ImplicationRelation::Implies => Cpx::UpperBound(CpxTime::Linear),
+        ImplicationRelation::Equivalent => Cpx::Equal,
+        ImplicationRelation::Excludes | ImplicationRelation::Unknown => return vec![],
+    };
+    let mut res = vec![];
+    for a in from {
+        for b in to {
+            if a.id != b.id {
+                res.extend(cpx_to_work_relations(a, b, &cpx));
+            }
+        }
+    }
+    res
+}
+
+/// Relations between parameters stated by the fact. An exact bound is stated only along
+/// with the single upper bound it gives.
+pub fn fact_relations<'a>(
+    fact: &'a Fact,
+    class_distances: &ClassDistances,
+) -> Option<FactRelations<'a>> {
+    match fact {
+        Fact::Relation(Relation::ParPar(subset, superset, cpx)) => {
+            Some((cpx_to_work_relations(subset, superset, cpx), cpx.bound()))
+        }
+        Fact::Relation(Relation::GcGc(subset, superset, inclusion)) => Some((
+            class_distance_relations(subset, superset, inclusion, class_distances),
+            None,
+        )),
+        _ => None,
+    }
+}
+
+/// Computes the closure of the stated relations between parameters, continuing from
+/// `previous` if given, in which case only the sources that are not `unchanged` are read.
+/// Returns the best known relation for every pair of parameters, the state of the closure
+/// whose partial results the relations refer to via their handles, and the results that
+/// contradicted an already known relation.
+#[allow(clippy::too_many_arguments)]
+pub fn process_relations(
+    composed_sets: &[(PreviewParameterId, Vec<PreviewParameter>)],
     transfers: &HashMap<TransferGroup, HashMap<PreviewParameterId, Vec<PreviewParameter>>>,
+    class_distances: &ClassDistances,
+    definitions: &DefinedRelations,
+    factoids: &Vec<(PreviewSourceId, Vec<Wrote>)>,
     sources: &HashMap<PreviewSourceId, Source>,
     preview_collection: &PreviewCollection,
-) -> (Vec<Relation>, Vec<PartialResult>) {
+    previous: Option<ReusedClosure>,
+) -> (ParameterArcs, ClosureState, Vec<CombinationError>) {
     trace!("processing relations");
-    let mut partial_results: Vec<PartialResult> = vec![];
-    let mut partial_results_builder = PartialResultsBuilder::new();
-    for (raw_source_id, showed) in &preview_collection.factoids {
-        match &showed.fact {
-            ShowedFact::Relation(status, relation) => {
-                if matches!(status, ShowedStatus::Conjectured) {
+    let continued = previous.is_some();
+    let (mut partial_results_builder, mut res, mut seeds, unchanged, dropped) = match previous {
+        Some(reused) => (
+            PartialResultsBuilder {
+                arr: reused.partial_results,
+            },
+            reused.relations,
+            reused.seeds,
+            reused.unchanged,
+            reused.dropped,
+        ),
+        None => {
+            let mut builder = PartialResultsBuilder::new();
+            let seeds = definitions
+                .iter()
+                .map(|(parameter, relation, cpx)| {
+                    builder
+                        .partial_result(
+                            CreatedBy::ByDefinition(parameter.clone()),
+                            cpx.clone(),
+                            relation.clone(),
+                        )
+                        .handle
+                })
+                .collect();
+            (builder, HashMap::new(), seeds, HashSet::new(), false)
+        }
+    };
+    for (raw_source_id, wrotes) in factoids {
+        if unchanged.contains(raw_source_id) {
+            continue;
+        }
+        let source = sources
+            .get(raw_source_id)
+            .unwrap_or_else(|| panic!("source not found {:?}", raw_source_id));
+        for wrote in wrotes {
+            for (showed_id, status, fact) in &wrote.facts {
+                if matches!(status, WroteStatus::Conjectured) {
                     continue;
                 }
-                if let Some(source) = sources.get(raw_source_id) {
-                    let work_relation =
-                        WorkRelation::new(&relation.subset.id, &relation.superset.id);
-                    let partial_result = partial_results_builder.partial_result(
-                        CreatedBy::Directly(source.preview()),
-                        relation.cpx.clone(),
-                        work_relation.clone(),
+                let Some((relations, bound)) = fact_relations(fact, class_distances) else {
+                    continue;
+                };
+                let created_by = match fact {
+                    Fact::Relation(Relation::GcGc(..)) => CreatedBy::ClassInclusion(source.preview(), showed_id.clone()),
+                    _ => CreatedBy::Directly(source.preview()),
+                };
+                for (work_relation, cpx_info) in relations {
+                    let partial_result = partial_results_builder.bounded_partial_result(
+                        created_by.clone(),
+                        cpx_info,
+                        work_relation,
+                        bound.cloned(),
                     );
-                    partial_results.push(partial_result);
-                } else {
-                    panic!("source not found {:?}", raw_source_id);
+                    seeds.push(partial_result.handle);
                 }
             }
-            ShowedFact::Definition(_, _) => (),
         }
     }
-    let mut res: HashMap<WorkRelation, PartialResult> = HashMap::new();
-    let mut progress = ProgressDisplay::new("processing", 22113);
-    for partial_result in partial_results {
-        let pair = partial_result.relation.clone();
-        debug!(
-            "processing relation from {} to {}",
-            pair.subset, pair.superset
-        );
-        let mut updated_relations: VecDeque<WorkRelation> = VecDeque::new();
-        // todo add progress in history when the collection is more complete
+    let mut contradictions: Vec<CombinationError> = vec![];
+    let mut updated_relations: VecDeque<WorkRelation> = VecDeque::new();
+    if dropped {
+        // results of transfers and sums of the kept relations may have been among the dropped ones
+        updated_relations.extend(res.keys().cloned());
+    }
+    for &handle in &seeds {
         add_and_update(
-            partial_result,
+            partial_results_builder.arr[handle].clone(),
             &mut res,
             &mut updated_relations,
+            &mut contradictions,
             &mut partial_results_builder,
         );
-        let mut improved_relations = 0;
-        while let Some(relation) = updated_relations.pop_front() {
-            improved_relations += 1;
-            if improved_relations >= 5000 {
-                panic!("5k updates during processing probably means a bug");
-            }
-            // apply the new or improved relation
-            for set in &preview_collection.preview_sets {
-                if set.id == relation.subset || set.id == relation.superset {
-                    continue;
-                }
-                // equivalence ab copies the new relation cd into ef
-                let xx = &relation.subset.clone();
-                let yy = &relation.superset.clone();
-                let zz = &set.id.clone();
-                for (x, y, z) in [
-                    (xx, yy, zz),
-                    (xx, zz, yy),
-                    (yy, xx, zz),
-                    (yy, zz, xx),
-                    (zz, xx, yy),
-                    (zz, yy, xx),
-                ] {
-                    let Some(ab) = res.get(&WorkRelation::new(x, y)) else {
-                        continue;
-                    };
-                    match ab.to_sourced() {
-                        SourcedCpxInfo::Equal { source } => {
-                            for (c, d, e, f) in
-                                [(z, x, z, y), (z, y, z, x), (x, z, y, z), (y, z, x, z)]
-                            {
-                                let Some(cd) = res.get(&WorkRelation::new(c, d)) else {
-                                    continue;
-                                };
-                                let created_by =
-                                    CreatedBy::SameThroughEquivalence(cd.handle, source.handle);
-                                let partial_result = partial_results_builder.partial_result(
-                                    created_by,
-                                    cd.cpx.clone(),
-                                    WorkRelation::new(e, f),
-                                );
-                                debug!("equivalence");
-                                add_and_update(
-                                    partial_result,
-                                    &mut res,
-                                    &mut updated_relations,
-                                    &mut partial_results_builder,
-                                );
-                            }
-                        }
-                        _ => continue,
-                    }
-                }
-                // inclusion ab and inclusion bc imply inclusion ac
-                for (a, b, c) in [
-                    (&set.id, &relation.subset, &relation.superset),
-                    (&relation.subset, &relation.superset, &set.id),
-                ] {
-                    let Some(ab) = res.get(&WorkRelation::new(a, b)) else {
-                        continue;
-                    };
-                    let Some(bc) = res.get(&WorkRelation::new(b, c)) else {
-                        continue;
-                    };
-                    if let (
-                        SourcedCpxInfo::Inclusion {
-                            mn: _,
-                            mx: Some((mxa, sra)),
-                        },
-                        SourcedCpxInfo::Inclusion {
-                            mn: _,
-                            mx: Some((mxb, srb)),
-                        },
-                    ) = (ab.to_sourced(), bc.to_sourced())
-                    {
-                        let rel = sra.relation.combine_serial(&srb.relation);
-                        let (a, b, time) = combine_serial((mxa, sra), (mxb, srb));
-                        let pr = partial_results_builder.partial_result(
-                            CreatedBy::TransitiveInclusion(a.handle, b.handle),
-                            CpxInfo::Inclusion {
-                                mn: None,
-                                mx: Some(time),
-                            },
-                            rel,
-                        );
-                        debug!(
-                            "inclusions {} {} + {} = {}",
-                            updated_relations.len(),
-                            a.handle,
-                            b.handle,
-                            c
-                        );
-                        add_and_update(
-                            pr,
-                            &mut res,
-                            &mut updated_relations,
-                            &mut partial_results_builder,
-                        );
-                    };
-                }
-                // inclusion ab and exclusion cd implies exclusion ef
-                for (a, b, c, d, e, f) in [
-                    (
-                        &relation.subset,
-                        &relation.superset,
-                        &set.id,
-                        &relation.superset,
-                        &set.id,
-                        &relation.subset,
-                    ),
-                    (
-                        &relation.subset,
-                        &relation.superset,
-                        &relation.subset,
-                        &set.id,
-                        &relation.superset,
-                        &set.id,
-                    ),
-                    (
-                        &set.id,
-                        &relation.superset,
-                        &relation.subset,
-                        &relation.superset,
-                        &relation.subset,
-                        &set.id,
-                    ),
-                    (
-                        &relation.subset,
-                        &set.id,
-                        &relation.subset,
-                        &relation.superset,
-                        &set.id,
-                        &relation.superset,
-                    ),
-                ] {
-                    let Some(ab) = res.get(&WorkRelation::new(a, b)) else {
-                        continue;
-                    };
-                    let Some(cd) = res.get(&WorkRelation::new(c, d)) else {
-                        continue;
-                    };
-                    let res_relation = WorkRelation::new(e, f);
-                    match (&ab.to_sourced(), &cd.to_sourced()) {
-                        (
-                            SourcedCpxInfo::Inclusion {
-                                mn: _,
-                                mx: Some((_, smx)),
-                            },
-                            SourcedCpxInfo::Exclusion { source },
-                        ) => {
-                            let created_by =
-                                CreatedBy::TransitiveExclusion(smx.handle, source.handle);
-                            let partial_result = partial_results_builder.partial_result(
-                                created_by,
-                                CpxInfo::Exclusion,
-                                res_relation,
-                            );
-                            debug!("exclusions");
-                            add_and_update(
-                                partial_result,
-                                &mut res,
-                                &mut updated_relations,
-                                &mut partial_results_builder,
-                            );
-                        }
-                        _ => continue,
-                    }
-                }
-            }
+    }
+    let interner = Interner::new(preview_collection.parameters_previews.keys());
+    let mut failed: HashSet<Derivation> = HashSet::new();
+    loop {
+        close(
+            &interner,
+            &mut res,
+            &mut updated_relations,
+            &mut contradictions,
+            &mut partial_results_builder,
+            &mut failed,
+        );
+        if updated_relations.is_empty() {
+            break;
+        }
+        let changed: Vec<WorkRelation> = updated_relations.drain(..).collect();
+        for relation in changed {
             // inclusion ab implies inclusion f(a)f(b) for a transfer f
-            if let Some(ab) = res.get(&relation) {
-                let new_partial_results =
-                    apply_transfers(transfers, ab, &mut partial_results_builder);
-                for partial_result in new_partial_results {
-                    debug!(
-                        "transfer from ({},{}) to ({},{})",
-                        relation.subset,
-                        relation.superset,
-                        partial_result.relation.subset,
-                        partial_result.relation.superset,
-                    );
-                    add_and_update(
-                        partial_result,
-                        &mut res,
-                        &mut updated_relations,
-                        &mut partial_results_builder,
-                    );
-                }
-            }
+            let mut derived = match res.get(&relation) {
+                Some(ab) => apply_transfers(transfers, ab, &mut partial_results_builder),
+                None => vec![],
+            };
             // inclusion ab and ac imply inclusion a(b+c)
-            for (composed_set, composed_elements) in composed_sets {
-                if &relation.subset == composed_set {
-                    continue;
-                }
-                let hash_components: HashSet<PreviewParameterId> =
-                    composed_elements.iter().map(|x| x.id.clone()).collect();
-                if hash_components.contains(&relation.superset) {
-                    debug!(
-                        "attempting composition {} {}",
-                        relation.subset, composed_set
-                    );
-                    let mut okay = true;
-                    let opt_cpxs: Vec<SourcedCpxInfo> = composed_elements
-                        .iter()
-                        .map(|x| res.get(&WorkRelation::new(&relation.subset, &x.id)))
-                        .filter_map(|x| {
-                            if let Some(a) = x {
-                                Some(a.to_sourced())
-                            } else {
-                                okay = false;
-                                None
-                            }
-                        })
-                        .collect();
-                    if okay && !opt_cpxs.is_empty() {
-                        let mut cpx: SourcedCpxInfo = opt_cpxs.first().unwrap().clone();
-                        for i in 1..opt_cpxs.len() {
-                            cpx = cpx.combine_plus(opt_cpxs.get(i).unwrap())
-                        }
-                        debug!("result: {:?}", cpx);
-                        let handles: Vec<usize> = composed_elements
-                            .iter()
-                            .map(|x| res.get(&WorkRelation::new(&relation.subset, &x.id)))
-                            .filter_map(|x| x.map(|a| a.handle))
-                            .collect();
-                        debug!("sum");
-                        let key = WorkRelation::new(&relation.subset, composed_set);
-                        let partial_result = partial_results_builder.partial_result(
-                            CreatedBy::SumInclusion(handles),
-                            cpx.into(),
-                            key,
-                        ); // todo check
-                        add_and_update(
-                            partial_result,
-                            &mut res,
-                            &mut updated_relations,
-                            &mut partial_results_builder,
-                        );
-                    }
-                }
+            derived.extend(sum_inclusions(
+                composed_sets,
+                &relation,
+                &res,
+                &mut partial_results_builder,
+            ));
+            for partial_result in derived {
+                add_and_update(
+                    partial_result,
+                    &mut res,
+                    &mut updated_relations,
+                    &mut contradictions,
+                    &mut partial_results_builder,
+                );
             }
         }
-        progress.increase(improved_relations);
     }
-    progress.done();
-    let result: Vec<Relation> = res
-        .values()
+    if continued && !contradictions.is_empty() {
+        info!("relations contradict each other, processing all relations");
+        return process_relations(
+            composed_sets,
+            transfers,
+            class_distances,
+            definitions,
+            factoids,
+            sources,
+            preview_collection,
+            None,
+        );
+    }
+    // sorted so that identical inputs produce identical pages and API files
+    let mut kept: Vec<&PartialResult> = res.values().collect();
+    kept.sort_by_cached_key(|x| (x.relation.subset.to_string(), x.relation.superset.to_string()));
+    let result = kept
+        .iter()
+        .copied()
         .map(|x: &PartialResult| {
             let subset = preview_collection
-                .preview_set_map
+                .parameters_previews
                 .get(&x.relation.subset)
                 .unwrap()
                 .clone();
             let superset = preview_collection
-                .preview_set_map
+                .parameters_previews
                 .get(&x.relation.superset)
                 .unwrap()
                 .clone();
-            Relation::new(subset, superset, x.to_sourced(), x.handle)
+            (subset, superset, x.to_sourced())
         })
         .collect();
-    (result, partial_results_builder.done())
+    let best = kept.iter().map(|x| x.handle).collect();
+    let closure = ClosureState {
+        partial_results: partial_results_builder.done(),
+        seeds,
+        best,
+        contradicted: !contradictions.is_empty(),
+    };
+    (result, closure, contradictions)
+}
+
+/// Adds the relations derived on bit matrices until no relation improves. Derivations
+/// that contradicted a known relation are remembered in `failed` and not retried.
+fn close(
+    interner: &Interner,
+    res: &mut HashMap<WorkRelation, PartialResult>,
+    updated_relations: &mut VecDeque<WorkRelation>,
+    contradictions: &mut Vec<CombinationError>,
+    partial_results_builder: &mut PartialResultsBuilder,
+    failed: &mut HashSet<Derivation>,
+) {
+    let mut rounds = 0;
+    loop {
+        rounds += 1;
+        let mut improved = false;
+        for derivation in derivations(interner, res) {
+            if failed.contains(&derivation) {
+                continue;
+            }
+            let Some(partial_result) = derive(interner, &derivation, res, partial_results_builder)
+            else {
+                continue;
+            };
+            let (updated, errors) = (updated_relations.len(), contradictions.len());
+            add_and_update(
+                partial_result,
+                res,
+                updated_relations,
+                contradictions,
+                partial_results_builder,
+            );
+            improved |= updated_relations.len() > updated;
+            if contradictions.len() > errors {
+                failed.insert(derivation);
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    debug!("closure stabilized after {} rounds", rounds);
+}
+
+/// Partial result for the derivation out of the currently known relations.
+fn derive(
+    interner: &Interner,
+    derivation: &Derivation,
+    res: &HashMap<WorkRelation, PartialResult>,
+    partial_results_builder: &mut PartialResultsBuilder,
+) -> Option<PartialResult> {
+    let get = |pair: (usize, usize)| res.get(&interner.relation(pair));
+    match derivation {
+        Derivation::Transitive(ab, bc, target) => {
+            let (
+                SourcedCpxInfo::Inclusion {
+                    mx: Some((mxa, sra)),
+                    ..
+                },
+                SourcedCpxInfo::Inclusion {
+                    mx: Some((mxb, srb)),
+                    ..
+                },
+            ) = (get(*ab)?.to_sourced(), get(*bc)?.to_sourced())
+            else {
+                return None;
+            };
+            let (a, b, time) = combine_serial((mxa, sra), (mxb, srb));
+            // the first pair bounds the middle parameter which bounds the last one
+            let bound = match (&a.bound, &b.bound) {
+                (Some(inner), Some(outer)) => Some(outer.compose(inner)),
+                _ => None,
+            };
+            Some(partial_results_builder.bounded_partial_result(
+                CreatedBy::TransitiveInclusion(a.handle, b.handle),
+                CpxInfo::Inclusion {
+                    mn: None,
+                    mx: Some(time),
+                },
+                interner.relation(*target),
+                bound,
+            ))
+        }
+        Derivation::ThroughEquivalence(copied, equal, target) => {
+            let SourcedCpxInfo::Equal { source } = get(*equal)?.to_sourced() else {
+                return None;
+            };
+            let cd = get(*copied)?;
+            Some(partial_results_builder.bounded_partial_result(
+                CreatedBy::SameThroughEquivalence(cd.handle, source.handle),
+                cd.cpx.clone(),
+                interner.relation(*target),
+                cd.bound.clone(),
+            ))
+        }
+        Derivation::Exclusion(inclusion, exclusion, target) => {
+            let (
+                SourcedCpxInfo::Inclusion {
+                    mx: Some((_, smx)), ..
+                },
+                SourcedCpxInfo::Exclusion { source },
+            ) = (get(*inclusion)?.to_sourced(), get(*exclusion)?.to_sourced())
+            else {
+                return None;
+            };
+            Some(partial_results_builder.partial_result(
+                CreatedBy::TransitiveExclusion(smx.handle, source.handle),
+                CpxInfo::Exclusion,
+                interner.relation(*target),
+            ))
+        }
+        Derivation::LowerBound(lower, linear, target) => {
+            let (
+                SourcedCpxInfo::Inclusion {
+                    mn: Some((mn, smn)), ..
+                },
+                SourcedCpxInfo::Inclusion {
+                    mx: Some((_, smx)), ..
+                },
+            ) = (get(*lower)?.to_sourced(), get(*linear)?.to_sourced())
+            else {
+                return None;
+            };
+            Some(partial_results_builder.partial_result(
+                CreatedBy::TransitiveLowerBound(smn.handle, smx.handle),
+                CpxInfo::Inclusion { mn: Some(mn), mx: None },
+                interner.relation(*target),
+            ))
+        }
+    }
+}
+
+/// Relations of `relation.subset` to all parts of an intersection sum up into a relation
+/// to the intersection.
+fn sum_inclusions(
+    composed_sets: &[(PreviewParameterId, Vec<PreviewParameter>)],
+    relation: &WorkRelation,
+    res: &HashMap<WorkRelation, PartialResult>,
+    partial_results_builder: &mut PartialResultsBuilder,
+) -> Vec<PartialResult> {
+    let mut result = vec![];
+    for (composed_set, composed_elements) in composed_sets {
+        if &relation.subset == composed_set
+            || !composed_elements.iter().any(|x| x.id == relation.superset)
+        {
+            continue;
+        }
+        let parts: Option<Vec<&PartialResult>> = composed_elements
+            .iter()
+            .map(|x| res.get(&WorkRelation::new(&relation.subset, &x.id)))
+            .collect();
+        let Some(parts) = parts else {
+            continue;
+        };
+        let Some(cpx) = parts
+            .iter()
+            .map(|x| x.to_sourced())
+            .reduce(|a, b| a.combine_plus(&b))
+        else {
+            continue;
+        };
+        debug!("sum {} {} {:?}", relation.subset, composed_set, cpx);
+        result.push(partial_results_builder.partial_result(
+            CreatedBy::SumInclusion(parts.iter().map(|x| x.handle).collect()),
+            cpx.into(),
+            WorkRelation::new(&relation.subset, composed_set),
+        ));
+    }
+    result
+}
+
+/// Splits the convenience input complexity into directed relations.
+fn cpx_to_work_relations(
+    subset: &PreviewParameter,
+    superset: &PreviewParameter,
+    cpx: &Cpx,
+) -> Vec<(WorkRelation, CpxInfo)> {
+    let forward = WorkRelation::new(&subset.id, &superset.id);
+    let backward = WorkRelation::new(&superset.id, &subset.id);
+    let upper_bound = |mx: &CpxTime| CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(mx.clone()),
+    };
+    match cpx {
+        Cpx::Bounds(a, b) => vec![(
+            forward,
+            CpxInfo::Inclusion {
+                mn: Some(a.clone()),
+                mx: Some(b.clone()),
+            },
+        )],
+        Cpx::Exactly(a) => vec![(
+            forward,
+            CpxInfo::Inclusion {
+                mn: Some(a.clone()),
+                mx: Some(a.clone()),
+            },
+        )],
+        Cpx::UpperBound(b) => vec![(forward, upper_bound(b))],
+        Cpx::UpperBoundBy(bound) => vec![(forward, upper_bound(&bound.time()))],
+        Cpx::LowerBound(a) => vec![(
+            forward,
+            CpxInfo::Inclusion {
+                mn: Some(a.clone()),
+                mx: None,
+            },
+        )],
+        Cpx::Todo => vec![(forward, upper_bound(&CpxTime::Exists))],
+        Cpx::Equal => {
+            if subset.id == superset.id {
+                vec![(forward, CpxInfo::Equal)]
+            } else {
+                vec![(forward, CpxInfo::Equal), (backward, CpxInfo::Equal)]
+            }
+        }
+        Cpx::Equivalent(first_to_second, second_to_first) => vec![
+            (forward, upper_bound(first_to_second)),
+            (backward, upper_bound(second_to_first)),
+        ],
+        Cpx::Exclusion => vec![(forward, CpxInfo::Exclusion)],
+        Cpx::Incomparable => vec![
+            (forward, CpxInfo::Exclusion),
+            (backward, CpxInfo::Exclusion),
+        ],
+        Cpx::StrictUpperBound(b) => vec![(forward, upper_bound(b)), (backward, CpxInfo::Exclusion)],
+    }
 }
+
+#[cfg(test)]
+#[path = "tests/inference.rs"]
+mod tests;
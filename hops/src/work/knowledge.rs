@@ -0,0 +1,239 @@
+//! How well established the relations are. A stated relation is as established as the
+//! status of its fact and a derivation is as established as the weakest of its premises,
+//! a relation takes the strongest of its derivations. For parameters this is found by
+//! closing the facts of each status and the better established ones on their own.
+
+use std::collections::HashMap;
+
+use crate::data::data::*;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::input::source::ImplicationRelation;
+use crate::work::bridge::bounded_classes;
+use crate::work::graph_relation::GcGcArcs;
+use crate::work::inference::{ParameterArcs, WorkRelation, fact_relations};
+use crate::work::transfer::ClassDistances;
+
+impl KnowledgeState {
+    /// State of a relation stated by a fact with the given status.
+    pub fn from_status(status: &WroteStatus) -> Self {
+        match status {
+            WroteStatus::Original | WroteStatus::Derivative => Self::Proved,
+            WroteStatus::Noted(NotedSource::Omitted) => Self::Folklore,
+            WroteStatus::Noted(_) | WroteStatus::TodoStatus => Self::Mentioned,
+            WroteStatus::Assumed => Self::Assumed,
+            WroteStatus::Conjectured => Self::Conjectured,
+        }
+    }
+
+    /// Higher is better established.
+    fn strength(&self) -> u32 {
+        match self {
+            Self::UnknownToHOPS | Self::Disproved => 0,
+            Self::Conjectured => 1,
+            Self::Assumed => 2,
+            Self::Mentioned => 3,
+            Self::Folklore => 4,
+            Self::Proved => 5,
+        }
+    }
+
+    pub fn weaker(self, other: Self) -> Self {
+        if other.strength() < self.strength() { other } else { self }
+    }
+
+    pub fn stronger(self, other: Self) -> Self {
+        if other.strength() > self.strength() { other } else { self }
+    }
+}
+
+fn insert_stronger<K: std::hash::Hash + Eq>(map: &mut HashMap<K, KnowledgeState>, key: K, state: KnowledgeState) -> bool {
+    let entry = map.entry(key).or_insert(KnowledgeState::UnknownToHOPS);
+    let old = *entry;
+    *entry = old.stronger(state);
+    *entry != old
+}
+
+/// States whose facts are closed on their own, strongest first. A relation that the facts
+/// of none of them give is assumed.
+pub const LEVELS: [KnowledgeState; 3] = [KnowledgeState::Proved, KnowledgeState::Folklore, KnowledgeState::Mentioned];
+
+/// Relations derived from the facts at least as established as each of `LEVELS`.
+pub type LeveledArcs = Vec<(KnowledgeState, ParameterArcs)>;
+
+/// Facts whose status is at least as established as `level`.
+pub fn factoids_at_least(
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    level: KnowledgeState,
+) -> Vec<(PreviewSourceId, Vec<Wrote>)> {
+    factoids
+        .iter()
+        .map(|(source_id, wrotes)| {
+            let wrotes = wrotes
+                .iter()
+                .map(|wrote| Wrote {
+                    text: wrote.text.clone(),
+                    page: wrote.page.clone(),
+                    facts: wrote
+                        .facts
+                        .iter()
+                        .filter(|(_, status, _)| KnowledgeState::from_status(status).strength() >= level.strength())
+                        .cloned()
+                        .collect(),
+                })
+                .collect();
+            (source_id.clone(), wrotes)
+        })
+        .collect()
+}
+
+/// State of the relation `cpx` of a pair, the strongest level whose facts give the pair
+/// a relation at least as good. Which derivations the closures kept does not matter.
+fn relation_state<'a>(
+    cpx: &SourcedCpxInfo,
+    leveled: impl IntoIterator<Item = (KnowledgeState, Option<&'a SourcedCpxInfo>)>,
+) -> KnowledgeState {
+    leveled
+        .into_iter()
+        .find(|(_, known)| {
+            known.is_some_and(|known| {
+                matches!(
+                    known.compare_to(cpx),
+                    Ok(ComparisonResult::Better | ComparisonResult::Equivalent)
+                )
+            })
+        })
+        .map_or(KnowledgeState::Assumed, |(state, _)| state)
+}
+
+/// State of every pair of parameters that has a relation or a conjecture. A conjecture
+/// about an unknown relation stays conjectured and one contradicting the relation is disproved.
+pub fn parameter_knowledge(
+    arc_parameter_parameter: &ParameterArcs,
+    leveled_arcs: &LeveledArcs,
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    class_distances: &ClassDistances,
+    parameters: &HashMap<PreviewParameterId, PreviewParameter>,
+) -> Vec<(PreviewParameter, PreviewParameter, KnowledgeState)> {
+    let mut conjectures: HashMap<WorkRelation, CpxInfo> = HashMap::new();
+    for (_, status, fact) in factoids.iter().flat_map(|(_, wrotes)| wrotes.iter().flat_map(|wrote| wrote.facts.iter())) {
+        if !matches!(status, WroteStatus::Conjectured) {
+            continue;
+        }
+        if let Some((relations, _)) = fact_relations(fact, class_distances) {
+            conjectures.extend(relations);
+        }
+    }
+    let leveled: Vec<(KnowledgeState, HashMap<_, _>)> = leveled_arcs
+        .iter()
+        .map(|(state, arcs)| (*state, arcs.iter().map(|(a, b, cpx)| ((&a.id, &b.id), cpx)).collect()))
+        .collect();
+    let mut res = vec![];
+    for (subset, superset, cpx) in arc_parameter_parameter {
+        let relation = WorkRelation::new(&subset.id, &superset.id);
+        let info = CpxInfo::from(cpx.clone());
+        let state = match conjectures.remove(&relation) {
+            Some(conjecture)
+                if matches!((conjecture.is_bounded(), info.is_bounded()), (Some(a), Some(b)) if a != b) =>
+            {
+                KnowledgeState::Disproved
+            }
+            Some(_) if info == CpxInfo::Unknown => KnowledgeState::Conjectured,
+            _ if info == CpxInfo::Unknown => KnowledgeState::UnknownToHOPS,
+            _ => relation_state(
+                cpx,
+                leveled
+                    .iter()
+                    .map(|(state, arcs)| (*state, arcs.get(&(&subset.id, &superset.id)).copied())),
+            ),
+        };
+        res.push((subset.clone(), superset.clone(), state));
+    }
+    for relation in conjectures.into_keys() {
+        if let (Some(subset), Some(superset)) = (parameters.get(&relation.subset), parameters.get(&relation.superset)) {
+            res.push((subset.clone(), superset.clone(), KnowledgeState::Conjectured));
+        }
+    }
+    res.sort_by_key(|(a, b, _)| (a.id.to_string(), b.id.to_string()));
+    res
+}
+
+/// State of every related pair of graph classes. Classes defined by parameters take the state
+/// of the parameters and inclusions that follow by transitivity are as established as the
+/// weakest inclusion along the best path.
+pub fn graph_class_knowledge(
+    arc_gc_gc: &GcGcArcs,
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    graph_classes: &[GraphClass],
+    knowledge_parameter_parameter: &[(PreviewParameter, PreviewParameter, KnowledgeState)],
+) -> Vec<(PreviewGraphClass, PreviewGraphClass, KnowledgeState)> {
+    let mut known: HashMap<(PreviewGraphClassId, PreviewGraphClassId), KnowledgeState> = HashMap::new();
+    for (_, status, fact) in factoids.iter().flat_map(|(_, wrotes)| wrotes.iter().flat_map(|wrote| wrote.facts.iter())) {
+        let Fact::Relation(Relation::GcGc(subset, superset, inclusion)) = fact else {
+            continue;
+        };
+        let state = KnowledgeState::from_status(status);
+        insert_stronger(&mut known, (subset.id.clone(), superset.id.clone()), state);
+        if inclusion.relation == ImplicationRelation::Equivalent {
+            insert_stronger(&mut known, (superset.id.clone(), subset.id.clone()), state);
+        }
+    }
+    let parameter_states: HashMap<(&PreviewParameterId, &PreviewParameterId), KnowledgeState> = knowledge_parameter_parameter
+        .iter()
+        .map(|(a, b, state)| ((&a.id, &b.id), *state))
+        .collect();
+    let bounded = bounded_classes(graph_classes);
+    for (a, pa) in &bounded {
+        for (b, pb) in &bounded {
+            if let Some(&state) = parameter_states.get(&(&pa.id, &pb.id)) {
+                insert_stronger(&mut known, (a.id.clone(), b.id.clone()), state);
+            }
+        }
+    }
+    let inclusions: Vec<(PreviewGraphClassId, PreviewGraphClassId)> = arc_gc_gc
+        .iter()
+        .flat_map(|(a, b, inclusion)| match inclusion.relation {
+            ImplicationRelation::Implies => vec![(a.id.clone(), b.id.clone())],
+            ImplicationRelation::Equivalent => vec![(a.id.clone(), b.id.clone()), (b.id.clone(), a.id.clone())],
+            ImplicationRelation::Excludes | ImplicationRelation::Unknown => vec![],
+        })
+        .collect();
+    let mut by_subset: HashMap<&PreviewGraphClassId, Vec<&PreviewGraphClassId>> = HashMap::new();
+    for (a, b) in &inclusions {
+        by_subset.entry(a).or_default().push(b);
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (a, b) in &inclusions {
+            let Some(&first) = known.get(&(a.clone(), b.clone())) else {
+                continue;
+            };
+            for &c in by_subset.get(b).into_iter().flatten() {
+                if let Some(&second) = known.get(&(b.clone(), c.clone()))
+                    && a != c
+                {
+                    changed |= insert_stronger(&mut known, (a.clone(), c.clone()), first.weaker(second));
+                }
+            }
+        }
+    }
+    let mut res: Vec<(PreviewGraphClass, PreviewGraphClass, KnowledgeState)> = arc_gc_gc
+        .iter()
+        .map(|(a, b, inclusion)| {
+            let state = match inclusion.relation {
+                ImplicationRelation::Unknown => KnowledgeState::UnknownToHOPS,
+                _ => known.get(&(a.id.clone(), b.id.clone())).copied().unwrap_or(KnowledgeState::Mentioned),
+            };
+            (a.clone(), b.clone(), state)
+        })
+        .collect();
+    res.sort_by_key(|(a, b, _)| (a.id.to_string(), b.id.to_string()));
+    res.dedup_by(|x, y| x.0.id == y.0.id && x.1.id == y.1.id);
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/knowledge.rs"]
+mod tests;
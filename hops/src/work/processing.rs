@@ -1,6 +1,7 @@
 //! Given raw data this module enriches and interconnects it.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 use biblatex::{Bibliography, Chunk, DateValue, Entry, PermissiveType, Person, Spanned};
 use log::info;
@@ -15,60 +16,51 @@ use crate::data::enums::*;
 use crate::data::id::*;
 use crate::data::link::{Link, Linkable};
 use crate::data::preview::*;
-use crate::data::simple_index::SimpleIndex;
+use crate::data::simple_index::{InclusionStatus, SimpleIndex};
 use crate::data::*;
 use crate::general::file;
 use crate::general::progress::ProgressDisplay;
+use crate::input::build::SUBGRAPH_RELATION_ID;
 use crate::input::raw::*;
 use crate::input::raw_enums::*;
 use crate::input::source::Def;
+use crate::input::source::ImplicationRelation;
 use crate::input::source::DefKind;
 use crate::input::source::Rel;
 use crate::input::source::RelKind;
 use crate::input::source::{RawFact, RawWrote};
 use crate::input::source::{RawSource, RawSourceKey};
+use crate::work::bridge::{bridge_gc_gc, bridge_gc_par};
+use crate::work::classification::classify_pairs;
+use crate::work::contradiction::collect_contradictions;
+use crate::work::history::{KnownBounds, drawing_bounds, factoids_until, history_fingerprint, known_bounds};
+use crate::work::incremental::{Fingerprint, ProcessedRelations, source_fingerprints, structure_fingerprint};
+use crate::work::inference::process_relations;
+use crate::work::knowledge::{LEVELS, LeveledArcs, factoids_at_least, graph_class_knowledge, parameter_knowledge};
+use crate::work::definition::definition_relations;
+use crate::work::graph_relation::{GraphRelationOrder, close_gc_gc};
 use crate::work::preview_collection::PreviewCollection;
+use crate::work::solvability::*;
+use crate::work::transfer::{class_distances, distance_to_transfers};
 
-fn process_parameter(
-    parameter: RawParameter,
-    preview_collection: &PreviewCollection,
+/// Restricts the facts of every source to those satisfying `should_save`,
+/// dropping the texts and sources which are left without facts.
+fn timeline(
     factoids: &Vec<(PreviewSourceId, Vec<Wrote>)>,
     source_map: &HashMap<PreviewSourceId, Source>,
-) -> Parameter {
-    let thisid = parameter.previewid();
-    let RawParameter {
-        id,
-        score,
-        name_core,
-        definition: raw_definition,
-        tags,
-    } = parameter;
+    should_save: impl Fn(&Fact) -> bool,
+) -> Vec<(PreviewSource, Vec<Wrote>)> {
     let mut timeline: Vec<(PreviewSource, Vec<Wrote>)> = Vec::new();
     for (source_id, wrotes) in factoids {
         let mut ok_wrote: Vec<Wrote> = Vec::new();
         for Wrote { text, page, facts } in wrotes {
-            let mut ok_facts: Vec<(PreviewShowedId, WroteStatus, Fact)> = Vec::new();
-            for (showed_id, status, fact) in facts {
-                let should_save = match fact {
-                    Fact::Definition(def) => match def{
-                        Definition::Parameter(preview) => preview.id == thisid,
-                        _ => false,
-                    }
-                    Fact::Relation(rel) => match rel {
-                        Relation::ParPar(pa, pb, _) => pa.id == thisid || pb.id == thisid,
-                        Relation::PropPar(_, preview, _) 
-                        | Relation::GcPar(_, preview, _)
-                        | Relation::ParProp(preview, _, _)
-                        | Relation::ProbPar(_, preview, _) => preview.id == thisid,
-                        _ => false
-                    },
-                };
-                if should_save {
-                    ok_facts.push((showed_id.clone(), status.clone(), fact.clone()));
-                }
-            }
+            let ok_facts: Vec<(PreviewShowedId, WroteStatus, Fact)> = facts
+                .iter()
+                .filter(|(_, _, fact)| should_save(fact))
+                .cloned()
+                .collect();
             if !ok_facts.is_empty() {
-                ok_wrote.push(Wrote{
+                ok_wrote.push(Wrote {
                     text: text.clone(),
                     page: page.clone(),
                     facts: ok_facts,
@@ -79,6 +71,37 @@ fn process_parameter(
             timeline.push((source_map.get(source_id).unwrap().preview(), ok_wrote));
         }
     }
+    timeline
+}
+
+fn process_parameter(
+    parameter: RawParameter,
+    preview_collection: &PreviewCollection,
+    factoids: &Vec<(PreviewSourceId, Vec<Wrote>)>,
+    source_map: &HashMap<PreviewSourceId, Source>,
+) -> Parameter {
+    let thisid = parameter.previewid();
+    let RawParameter {
+        id,
+        score,
+        name_core,
+        definition: raw_definition,
+        tags,
+    } = parameter;
+    let timeline = timeline(factoids, source_map, |fact| match fact {
+        Fact::Definition(def) => match def {
+            Definition::Parameter(preview) => preview.id == thisid,
+            _ => false,
+        },
+        Fact::Relation(rel) => match rel {
+            Relation::ParPar(pa, pb, _) => pa.id == thisid || pb.id == thisid,
+            Relation::PropPar(_, preview, _)
+            | Relation::GcPar(_, preview, _)
+            | Relation::ParProp(preview, _, _)
+            | Relation::ProbPar(_, preview, _) => preview.id == thisid,
+            _ => false,
+        },
+    });
     Parameter {
         id,
         name_core,
@@ -175,6 +198,39 @@ fn process_logic_fragment(lf: RawLogicFragment) -> LogicFragment {
     }
 }
 
+fn process_problem(
+    problem: RawProblem,
+    preview_collection: &PreviewCollection,
+    factoids: &Vec<(PreviewSourceId, Vec<Wrote>)>,
+    source_map: &HashMap<PreviewSourceId, Source>,
+) -> Problem {
+    let thisid = problem.previewid();
+    let RawProblem {
+        id,
+        name_core,
+        definition,
+    } = problem;
+    let timeline = timeline(factoids, source_map, |fact| match fact {
+        Fact::Definition(Definition::Problem(preview)) => preview.id == thisid,
+        Fact::Relation(Relation::ProbProb(pa, pb, _)) => pa.id == thisid || pb.id == thisid,
+        Fact::Relation(Relation::ProbProp(preview, _, _) | Relation::ProbPar(preview, _, _)) => {
+            preview.id == thisid
+        }
+        _ => false,
+    });
+    Problem {
+        id,
+        name_core,
+        definition: match definition {
+            RawProblemDefinition::Text(text) => ProblemDefinition::Problem(text),
+            RawProblemDefinition::ModelChecking(lf) => ProblemDefinition::ModelChecking(
+                preview_collection.logic_fragments_previews.get(&lf).unwrap().clone(),
+            ),
+        },
+        timeline,
+    }
+}
+
 fn process_operation(op: RawOperation) -> Operation {
     let RawOperation {
         id,
@@ -217,6 +273,7 @@ fn process_parametric_parameter(
 fn process_parametric_graph_class(
     pgc: RawParametricGraphClass,
     preview_collection: &PreviewCollection,
+    graph_relation_order: &GraphRelationOrder,
 ) -> ParametricGraphClass {
     let RawParametricGraphClass {
         id,
@@ -226,6 +283,11 @@ fn process_parametric_graph_class(
         tags,
         definition,
     } = pgc;
+    let also_closed_under = graph_relation_order
+        .also_closed_under(&closed_under)
+        .iter()
+        .map(|x| preview_collection.graph_relations_previews.get(x).unwrap().clone())
+        .collect();
     let closed_under = preview_collection
         .graph_relations_previews
         .get(&closed_under)
@@ -236,6 +298,7 @@ fn process_parametric_graph_class(
         score,
         name_core,
         closed_under,
+        also_closed_under,
         tags: tags
             .iter()
             .map(|x| preview_collection.tags_previews.get(x).unwrap().clone())
@@ -287,25 +350,14 @@ impl RawSource {
                 description: description.clone(),
             },
         };
-        let mut showed = vec![];
-        // for (preview_source_id, preview_showed) in &preview_collection.factoids {
-        //     if preview_source_id == &self.id.preview() {
-        //         showed.push(preview_showed.clone());
-        //     }
-        // }
         Source {
             id,
             name_core: NameCore::new(&sourcekey.get_name()),
             sourcekey,
-            wrote: showed,
+            wrote: vec![], // filled in by process_raw_data once the facts are converted
             time,
-            drawings: vec![],
+            drawings: vec![], // filled in by process_raw_data as well
             score,
-            // drawings: source // todo
-            //     .drawings
-            //     .iter()
-            //     .map(|drawing| Drawing::from(drawing, &preview_collection.preview_set_map))
-            //     .collect(),
         }
     }
 }
@@ -321,7 +373,30 @@ where
         .collect()
 }
 
-pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -> Data {
+/// Classes of parameters that upper bound each other, the best scored parameter goes first.
+fn equivalent_parameters(
+    arc_parameter_parameter: &[(PreviewParameter, PreviewParameter, SourcedCpxInfo)],
+) -> Vec<Vec<PreviewParameter>> {
+    let relations: Vec<(PreviewParameter, PreviewParameter, InclusionStatus)> = arc_parameter_parameter
+        .iter()
+        .filter(|(_, _, cpx)| CpxInfo::from(cpx.clone()).get_mx().is_some())
+        .map(|(a, b, _)| (a.clone(), b.clone(), InclusionStatus::Inclusion))
+        .collect();
+    let mut res = SimpleIndex::new(&relations).get_eqclasses();
+    for class in &mut res {
+        class.sort_by_key(|x| (Reverse(x.score), x.id.to_string()));
+    }
+    res.sort_by_key(|class| class[0].id.to_string());
+    res
+}
+
+/// Processes the collected data, reusing the relations of a `previous` run where the sources
+/// did not change. Also returns the relations to be reused by the next run.
+pub fn process_raw_data(
+    rawdata: RawData,
+    bibliography: &Option<Bibliography>,
+    previous: Option<ProcessedRelations>,
+) -> (Data, ProcessedRelations) {
     let preview_collection = PreviewCollection::new(&rawdata);
     let RawData {
         graph_class_relations: raw_graph_class_relations,
@@ -342,7 +417,7 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
         provider_links: raw_provider_links,
         problems: raw_problems,
     } = rawdata;
-    let sources = convert_to_id_map(
+    let mut sources = convert_to_id_map(
         raw_sources
             .into_iter()
             .map(|source| source.process(bibliography, &preview_collection))
@@ -352,7 +427,18 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
         (source_id, wrotes.into_iter().map(|wrote|{
             Wrote::from(wrote, &sources, &preview_collection)
         }).collect())
-    }).collect();    let raw_parameters_map = convert_to_id_map(raw_parameters);
+    }).collect();
+    for (source_id, wrotes) in &factoids {
+        if let Some(source) = sources.get_mut(source_id) {
+            source.wrote.extend(wrotes.iter().cloned());
+        }
+    }
+    for (source_id, drawings) in raw_drawings {
+        if let Some(source) = sources.get_mut(&source_id) {
+            source.drawings.extend(drawings.into_iter().map(|x| Drawing::from(x, &preview_collection)));
+        }
+    }
+    let raw_parameters_map = convert_to_id_map(raw_parameters);
     let mut definitions_map: HashMap<DefKind, Vec<Definition>> = HashMap::new();
     let mut relations_map: HashMap<RelKind, Vec<Relation>> = HashMap::new();
     for (source_id, wrotes) in &factoids {
@@ -418,13 +504,151 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
     add_tag_links(&raw_graphs, &mut tag_map);
     add_tag_links(&raw_parametric_parameters, &mut tag_map);
     add_tag_links(&raw_parametric_graph_class, &mut tag_map);
-    // let (relations, partial_results) =
-    //     process_relations(&composed_sets, &transfers, &sources, &preview_collection);
+    let composed_sets: Vec<(PreviewParameterId, Vec<PreviewParameter>)> = raw_parameters_map
+        .values()
+        .filter_map(|parameter| match &parameter.definition {
+            RawParameterDefinition::IntersectionParameters(ids) => Some((
+                parameter.id.preview(),
+                ids.iter()
+                    .filter_map(|id| preview_collection.parameters_previews.get(id).cloned())
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect();
+    let transfers = distance_to_transfers(raw_parameters_map.values(), &preview_collection);
+    let graph_relations: Vec<GraphRelation> = raw_graph_relations
+        .into_iter()
+        .map(|gr| process_graph_relation(gr, &preview_collection))
+        .collect();
+    let graph_relation_order = GraphRelationOrder::new(&graph_relations);
+    let plain = PreviewGraphRelationId::from(SUBGRAPH_RELATION_ID);
+    let plain_relations: HashSet<PreviewGraphRelationId> = graph_relations
+        .iter()
+        .map(|relation| relation.id.preview())
+        .filter(|relation| graph_relation_order.implies(relation, &plain))
+        .collect();
+    let class_distances = class_distances(raw_parameters_map.values(), plain_relations, &preview_collection);
+    let definitions = definition_relations(raw_parameters_map.values(), &preview_collection);
+    let parameter_previews: Vec<PreviewParameter> =
+        preview_collection.parameters_previews.values().cloned().collect();
+    let structure = structure_fingerprint(
+        &parameter_previews,
+        &composed_sets,
+        &transfers,
+        &class_distances,
+        &definitions,
+    );
+    let source_fingerprints = source_fingerprints(&factoids, &sources);
+    let (mut previous_history, mut reused): (HashMap<Fingerprint, KnownBounds>, _) = match previous {
+        Some(mut previous) => {
+            let history = std::mem::take(&mut previous.history).into_iter().collect();
+            (history, previous.reuse(structure, &source_fingerprints))
+        }
+        None => (HashMap::new(), HashMap::new()),
+    };
+    let (arc_parameter_parameter, closure, combination_errors) = process_relations(
+        &composed_sets,
+        &transfers,
+        &class_distances,
+        &definitions,
+        &factoids,
+        &sources,
+        &preview_collection,
+        reused.remove(&KnowledgeState::Assumed),
+    );
+    let partial_results = closure.partial_results.clone();
+    let mut closures = vec![(KnowledgeState::Assumed, closure)];
+    // a relation is as established as the weakest facts it follows from, so the closure is
+    // repeated on the better established facts alone
+    let mut leveled_arcs: LeveledArcs = vec![];
+    for level in LEVELS {
+        let (arcs, closure, _) = process_relations(
+            &composed_sets,
+            &transfers,
+            &class_distances,
+            &definitions,
+            &factoids_at_least(&factoids, level),
+            &sources,
+            &preview_collection,
+            reused.remove(&level),
+        );
+        leveled_arcs.push((level, arcs));
+        closures.push((level, closure));
+    }
+    // figures are compared with what followed from the sources up to their date, which is
+    // derived once for every date and kept while none of those sources change
+    let mut drawn_by_date: BTreeMap<Date, Vec<PreviewParameter>> = BTreeMap::new();
+    for source in sources.values() {
+        for drawing in &source.drawings {
+            drawn_by_date
+                .entry(source.time.clone())
+                .or_default()
+                .extend(drawing.parameters.iter().cloned());
+        }
+    }
+    let mut history: Vec<(Fingerprint, KnownBounds)> = vec![];
+    let mut known_then: HashMap<Date, usize> = HashMap::new();
+    for (date, mut drawn) in drawn_by_date {
+        drawn.sort_by_key(|x| x.id.to_string());
+        drawn.dedup();
+        let key = history_fingerprint(structure, &source_fingerprints, &sources, &date, &drawn);
+        let known = match previous_history.remove(&key) {
+            Some(known) => known,
+            None => {
+                let (arcs, _, _) = process_relations(
+                    &composed_sets,
+                    &transfers,
+                    &class_distances,
+                    &definitions,
+                    &factoids_until(&factoids, &sources, &date),
+                    &sources,
+                    &preview_collection,
+                    None,
+                );
+                known_bounds(&drawn, &arcs)
+            }
+        };
+        known_then.insert(date, history.len());
+        history.push((key, known));
+    }
+    for source in sources.values_mut() {
+        let Some(&idx) = known_then.get(&source.time) else {
+            continue;
+        };
+        for drawing in &mut source.drawings {
+            drawing.known_then = drawing_bounds(&history[idx].1, &drawing.parameters);
+        }
+    }
+    let processed_relations = ProcessedRelations {
+        structure,
+        sources: source_fingerprints,
+        closures,
+        history,
+    };
+    let contradictions = collect_contradictions(combination_errors);
+    let equivalent_parameters = equivalent_parameters(&arc_parameter_parameter);
+    let pair_classes = classify_pairs(&arc_parameter_parameter);
+    let knowledge_parameter_parameter = parameter_knowledge(
+        &arc_parameter_parameter,
+        &leveled_arcs,
+        &factoids,
+        &class_distances,
+        &preview_collection.parameters_previews,
+    );
+    let arc_problem_parameter = process_parameterized_solvability(
+        stated_parameterized_solvability(&factoids, &sources),
+        &arc_parameter_parameter,
+    );
     let parameters = raw_parameters_map
         .into_values()
         .map(|parameter| process_parameter(parameter, &preview_collection, &factoids, &sources))
         .collect();
-    let graph_classes = raw_graph_classes
+    let problems: Vec<Problem> = raw_problems
+        .into_iter()
+        .map(|problem| process_problem(problem, &preview_collection, &factoids, &sources))
+        .collect();
+    let graph_classes: Vec<GraphClass> = raw_graph_classes
         .into_iter()
         .map(|gc| process_graph_class(gc, &preview_collection))
         .collect();
@@ -441,9 +665,20 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
         .map(process_logic_fragment)
         .collect();
     let operations: Vec<Operation> = raw_operations.into_iter().map(process_operation).collect();
-    let graph_relations: Vec<GraphRelation> = raw_graph_relations
+    let graph_relation_previews: HashMap<PreviewGraphRelationId, PreviewGraphRelation> = graph_relations
+        .iter()
+        .map(|gr| (gr.id.preview(), gr.preview()))
+        .collect();
+    let arc_grrel_grrel = graph_relation_order
+        .pairs()
         .into_iter()
-        .map(|gr| process_graph_relation(gr, &preview_collection))
+        .map(|(a, b)| {
+            (
+                graph_relation_previews[&a].clone(),
+                graph_relation_previews[&b].clone(),
+                ImplicationRelation::Implies,
+            )
+        })
         .collect();
     let parametric_parameters: Vec<ParametricParameter> = raw_parametric_parameters
         .into_iter()
@@ -451,9 +686,8 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
         .collect();
     let parametric_graph_class: Vec<ParametricGraphClass> = raw_parametric_graph_class
         .into_iter()
-        .map(|pgc| process_parametric_graph_class(pgc, &preview_collection))
+        .map(|pgc| process_parametric_graph_class(pgc, &preview_collection, &graph_relation_order))
         .collect();
-    let mut arc_parameter_parameter = Vec::new();
     let mut arc_lf_lf = Vec::new();
     let mut arc_op_op = Vec::new();
     let mut arc_graph_graph = Vec::new();
@@ -465,8 +699,6 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
     let mut arc_parameter_gcprop = Vec::new();
     let mut arc_gc_par = Vec::new();
     let mut arc_problem_problem = Vec::new();
-    let mut arc_problem_gcprop = Vec::new();
-    let mut arc_problem_parameter = Vec::new();
     let mut arc_gcprop_parameter = Vec::new();
     for (k, col) in relations_map {
         for x in col {
@@ -477,20 +709,38 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
                 Relation::GcGc(f, t, d) => arc_gc_gc.push((f.clone(), t.clone(), d.clone())),
                 Relation::GrGc(f, t, d) => arc_graph_gc.push((f.clone(), t.clone(), d.clone())),
                 Relation::PgcPgc(f, t, d) => arc_pargc_pargc.push((f.clone(), t.clone(), d.clone())),
-                Relation::ParPar(f, t, d) => arc_parameter_parameter.push((f.clone(), t.clone(), d.clone())),
+                Relation::ParPar(..) => (), // inferred by process_relations
                 Relation::PropProp(f, t, d) => arc_gcprop_gcprop.push((f.clone(), t.clone(), d.clone())),
                 Relation::GcProp(f, t, d) => arc_gc_gcprop.push((f.clone(), t.clone(), d.clone())),
                 Relation::GcPar(f, t, d) => arc_gc_par.push((f.clone(), t.clone(), d.clone())),
                 Relation::ParProp(f, t, d) => arc_parameter_gcprop.push((f.clone(), t.clone(), d.clone())),
                 Relation::ProbProb(f, t, d) => arc_problem_problem.push((f.clone(), t.clone(), d.clone())),
-                Relation::ProbProp(f, t, d) => arc_problem_gcprop.push((f.clone(), t.clone(), d.clone())),
-                Relation::ProbPar(f, t, d) => arc_problem_parameter.push((f.clone(), t.clone(), d.clone())),
+                Relation::ProbProp(..) => (), // closed by process_classical_solvability
+                Relation::ProbPar(..) => (), // closed by process_parameterized_solvability
                 Relation::PropPar(f, t, d) => arc_gcprop_parameter.push((f.clone(), t.clone(), d.clone())),
             }
         }
     }
-    Data::new(DataFields {
+    let arc_gc_par = bridge_gc_par(arc_gc_par, &graph_classes, &arc_parameter_parameter);
+    arc_gc_gc.extend(bridge_gc_gc(
+        &graph_classes,
+        &arc_parameter_parameter,
+        &PreviewGraphRelationId::from(SUBGRAPH_RELATION_ID),
+    ));
+    let arc_gc_gc = close_gc_gc(arc_gc_gc, &graph_relation_order);
+    let knowledge_gc_gc = graph_class_knowledge(&arc_gc_gc, &factoids, &graph_classes, &knowledge_parameter_parameter);
+    let mut inclusions = stated_inclusions(&factoids, &sources);
+    add_class_inclusions(&mut inclusions, &arc_gc_gc, &knowledge_gc_gc, |relation| {
+        graph_relation_order.implies(relation, &plain)
+    });
+    let arc_problem_gcprop = process_classical_solvability(
+        stated_classical_solvability(&factoids, &sources),
+        &inclusions,
+        &preview_collection.graph_class_properties_previews,
+    );
+    let data = Data::new(DataFields {
         tags: tag_map.into_values().collect(),
+        problems,
         providers,
         parametric_parameters,
         parametric_graph_class,
@@ -503,8 +753,15 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
         sources: sources.into_values().collect(),
         graph_class_properties,
         arc_parameter_parameter,
+        partial_results,
+        equivalent_parameters,
+        pair_classes,
+        knowledge_parameter_parameter,
+        knowledge_gc_gc,
+        contradictions,
         arc_lf_lf,
         arc_op_op,
+        arc_grrel_grrel,
         arc_graph_graph,
         arc_gc_gc,
         arc_graph_gc,
@@ -517,5 +774,10 @@ pub fn process_raw_data(rawdata: RawData, bibliography: &Option<Bibliography>) -
         arc_problem_gcprop,
         arc_problem_parameter,
         arc_gcprop_parameter,
-    })
+    });
+    (data, processed_relations)
 }
+
+#[cfg(test)]
+#[path = "tests/processing.rs"]
+mod tests;
@@ -0,0 +1,351 @@
+//! Moves complexity results of problems along the relations between parameters
+//! and along the inclusions of graph classes.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::data::data::*;
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::input::source::{ClassicalSolvability, EquivalenceRelation, ImplicationRelation};
+use crate::input::source::ParameterizedSolvability;
+use crate::work::graph_relation::GcGcArcs;
+use crate::work::inference::ParameterArcs;
+
+pub type ProblemParameterArcs = Vec<(
+    PreviewProblem,
+    PreviewParameter,
+    SourcedSolvability<ParameterizedSolvability>,
+)>;
+
+pub type ProblemPropertyArcs = Vec<(
+    PreviewProblem,
+    PreviewGraphClassProperty,
+    SourcedSolvability<ClassicalSolvability>,
+)>;
+
+/// Graph classes and their properties are both sets of graphs which may include one another.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GraphSet {
+    Class(PreviewGraphClassId),
+    Property(PreviewGraphClassPropertyId),
+}
+
+/// Inclusions of sets, maps each set to its direct supersets along with the relation that proves it.
+pub type Inclusions = HashMap<GraphSet, Vec<(GraphSet, Relation)>>;
+
+impl ParameterizedSolvability {
+    /// Positive results move to parameters that bound the parameter,
+    /// hardness results move to the parameters that are bounded by it.
+    pub fn is_tractable(&self) -> bool {
+        match self {
+            Self::Polynomial | Self::Fpt | Self::Xp => true,
+            Self::Whard | Self::ParaNpHard => false,
+        }
+    }
+
+    /// Higher is a stronger statement among results of the same tractability.
+    pub fn strength(&self) -> u32 {
+        match self {
+            Self::Xp => 0,
+            Self::Fpt => 1,
+            Self::Polynomial => 2,
+            Self::Whard => 0,
+            Self::ParaNpHard => 1,
+        }
+    }
+}
+
+fn stated_facts<'a>(
+    factoids: &'a [(PreviewSourceId, Vec<Wrote>)],
+    sources: &'a HashMap<PreviewSourceId, Source>,
+) -> impl Iterator<Item = (&'a Source, &'a Fact)> {
+    factoids.iter().flat_map(move |(source_id, wrotes)| {
+        let source = sources
+            .get(source_id)
+            .unwrap_or_else(|| panic!("source not found {:?}", source_id));
+        wrotes
+            .iter()
+            .flat_map(|wrote| wrote.facts.iter())
+            .filter(|(_, status, _)| !matches!(status, WroteStatus::Conjectured))
+            .map(move |(_, _, fact)| (source, fact))
+    })
+}
+
+/// Collects the results of parameterized problems as they were stated in the sources.
+pub fn stated_parameterized_solvability(
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    sources: &HashMap<PreviewSourceId, Source>,
+) -> ProblemParameterArcs {
+    stated_facts(factoids, sources)
+        .filter_map(|(source, fact)| match fact {
+            Fact::Relation(Relation::ProbPar(problem, parameter, solvability)) => Some((
+                problem.clone(),
+                parameter.clone(),
+                SourcedSolvability {
+                    solvability: solvability.clone(),
+                    origin: SolvabilityOrigin::Directly(source.preview()),
+                },
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Collects the results of problems on graph classes as they were stated in the sources.
+pub fn stated_classical_solvability(
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    sources: &HashMap<PreviewSourceId, Source>,
+) -> ProblemPropertyArcs {
+    stated_facts(factoids, sources)
+        .filter_map(|(source, fact)| match fact {
+            Fact::Relation(Relation::ProbProp(problem, property, solvability)) => Some((
+                problem.clone(),
+                property.clone(),
+                SourcedSolvability {
+                    solvability: solvability.clone(),
+                    origin: SolvabilityOrigin::Directly(source.preview()),
+                },
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn add_inclusion(inclusions: &mut Inclusions, a: GraphSet, b: GraphSet, both_ways: bool, relation: &Relation) {
+    if both_ways {
+        inclusions
+            .entry(b.clone())
+            .or_default()
+            .push((a.clone(), relation.clone()));
+    }
+    inclusions.entry(a).or_default().push((b, relation.clone()));
+}
+
+/// Collects the stated implications between graph class properties and the properties
+/// that are equivalent to graph classes.
+pub fn stated_inclusions(
+    factoids: &[(PreviewSourceId, Vec<Wrote>)],
+    sources: &HashMap<PreviewSourceId, Source>,
+) -> Inclusions {
+    let mut res: Inclusions = HashMap::new();
+    for (_, fact) in stated_facts(factoids, sources) {
+        let Fact::Relation(relation) = fact else {
+            continue;
+        };
+        match relation {
+            Relation::PropProp(a, b, implication) => {
+                let (a, b) = (GraphSet::Property(a.id.clone()), GraphSet::Property(b.id.clone()));
+                match implication {
+                    ImplicationRelation::Implies => add_inclusion(&mut res, a, b, false, relation),
+                    ImplicationRelation::Equivalent => add_inclusion(&mut res, a, b, true, relation),
+                    ImplicationRelation::Excludes | ImplicationRelation::Unknown => {}
+                }
+            }
+            Relation::GcProp(gc, property, EquivalenceRelation::Equivalent) => add_inclusion(
+                &mut res,
+                GraphSet::Class(gc.id.clone()),
+                GraphSet::Property(property.id.clone()),
+                true,
+                relation,
+            ),
+            _ => {}
+        }
+    }
+    res
+}
+
+/// Adds the closed inclusions of graph classes, including the derived ones, that give a plain
+/// inclusion of the graphs and are established better than a conjecture.
+pub fn add_class_inclusions(
+    inclusions: &mut Inclusions,
+    arc_gc_gc: &GcGcArcs,
+    knowledge_gc_gc: &[(PreviewGraphClass, PreviewGraphClass, KnowledgeState)],
+    is_plain: impl Fn(&PreviewGraphRelationId) -> bool,
+) {
+    let knowledge: HashMap<(&PreviewGraphClassId, &PreviewGraphClassId), KnowledgeState> = knowledge_gc_gc
+        .iter()
+        .map(|(a, b, state)| ((&a.id, &b.id), *state))
+        .collect();
+    for (a, b, inclusion) in arc_gc_gc {
+        if !is_plain(&inclusion.graph_relation)
+            || matches!(
+                knowledge.get(&(&a.id, &b.id)),
+                Some(KnowledgeState::Conjectured | KnowledgeState::Disproved)
+            )
+        {
+            continue;
+        }
+        let relation = Relation::GcGc(a.clone(), b.clone(), inclusion.clone());
+        let (a, b) = (GraphSet::Class(a.id.clone()), GraphSet::Class(b.id.clone()));
+        match inclusion.relation {
+            ImplicationRelation::Implies => add_inclusion(inclusions, a, b, false, &relation),
+            ImplicationRelation::Equivalent => add_inclusion(inclusions, a, b, true, &relation),
+            ImplicationRelation::Excludes | ImplicationRelation::Unknown => {}
+        }
+    }
+}
+
+/// Handle of the partial result which shows that the subset upper bounds the superset.
+fn upper_bound_handle(cpx: &SourcedCpxInfo) -> Option<usize> {
+    match cpx {
+        SourcedCpxInfo::Equal { source } => Some(source.handle),
+        SourcedCpxInfo::Inclusion {
+            mx: Some((_, source)),
+            ..
+        } => Some(source.handle),
+        _ => None,
+    }
+}
+
+/// Closes the stated results under the bounds between parameters. If a problem is
+/// tractable by p and q bounds p then it is tractable by q. If it is hard by q and
+/// q bounds p then it is hard by p. For every problem, parameter and tractability
+/// only the strongest result is kept.
+pub fn process_parameterized_solvability(
+    stated: ProblemParameterArcs,
+    arc_parameter_parameter: &ParameterArcs,
+) -> ProblemParameterArcs {
+    let mut res: ProblemParameterArcs = vec![];
+    let mut indices: HashMap<(PreviewProblemId, PreviewParameterId, bool), usize> = HashMap::new();
+    let mut insert = |problem: &PreviewProblem,
+                      parameter: &PreviewParameter,
+                      sourced: SourcedSolvability<ParameterizedSolvability>| {
+        let key = (
+            problem.id.clone(),
+            parameter.id.clone(),
+            sourced.solvability.is_tractable(),
+        );
+        match indices.get(&key) {
+            Some(&idx) => {
+                if res[idx].2.solvability.strength() < sourced.solvability.strength() {
+                    res[idx].2 = sourced;
+                }
+            }
+            None => {
+                indices.insert(key, res.len());
+                res.push((problem.clone(), parameter.clone(), sourced));
+            }
+        }
+    };
+    for (problem, parameter, sourced) in &stated {
+        insert(problem, parameter, sourced.clone());
+    }
+    for (problem, parameter, sourced) in &stated {
+        let SolvabilityOrigin::Directly(source) = &sourced.origin else {
+            continue;
+        };
+        let tractable = sourced.solvability.is_tractable();
+        for (subset, superset, cpx) in arc_parameter_parameter {
+            if subset.id == superset.id {
+                continue;
+            }
+            let (from, to) = if tractable {
+                (superset, subset)
+            } else {
+                (subset, superset)
+            };
+            if from.id != parameter.id {
+                continue;
+            }
+            let Some(bound) = upper_bound_handle(cpx) else {
+                continue;
+            };
+            insert(
+                problem,
+                to,
+                SourcedSolvability {
+                    solvability: sourced.solvability.clone(),
+                    origin: SolvabilityOrigin::AlongParameterBound {
+                        parameter: parameter.clone(),
+                        source: source.clone(),
+                        bound,
+                    },
+                },
+            );
+        }
+    }
+    res
+}
+
+/// All sets reachable from `start` by following the edges, each with the chain of facts that
+/// leads to it.
+fn reachable(start: &GraphSet, edges: &Inclusions) -> Vec<(GraphSet, Vec<Relation>)> {
+    let mut res = vec![];
+    let mut visited: HashSet<GraphSet> = HashSet::from([start.clone()]);
+    let mut queue: VecDeque<(GraphSet, Vec<Relation>)> = VecDeque::from([(start.clone(), vec![])]);
+    while let Some((set, path)) = queue.pop_front() {
+        for (next, relation) in edges.get(&set).into_iter().flatten() {
+            if visited.insert(next.clone()) {
+                let mut next_path = path.clone();
+                next_path.push(relation.clone());
+                queue.push_back((next.clone(), next_path.clone()));
+                res.push((next.clone(), next_path));
+            }
+        }
+    }
+    res
+}
+
+/// Closes the stated results under the inclusions of graph classes. Polynomial
+/// algorithms work on all subclasses and NP-hardness holds for all superclasses.
+pub fn process_classical_solvability(
+    stated: ProblemPropertyArcs,
+    inclusions: &Inclusions,
+    properties: &HashMap<PreviewGraphClassPropertyId, PreviewGraphClassProperty>,
+) -> ProblemPropertyArcs {
+    let mut reversed: Inclusions = HashMap::new();
+    for (subset, supersets) in inclusions {
+        for (superset, relation) in supersets {
+            reversed
+                .entry(superset.clone())
+                .or_default()
+                .push((subset.clone(), relation.clone()));
+        }
+    }
+    let mut known: HashSet<(PreviewProblemId, PreviewGraphClassPropertyId, ClassicalSolvability)> =
+        stated
+            .iter()
+            .map(|(problem, property, sourced)| {
+                (problem.id.clone(), property.id.clone(), sourced.solvability.clone())
+            })
+            .collect();
+    let mut res = stated.clone();
+    for (problem, property, sourced) in &stated {
+        let SolvabilityOrigin::Directly(source) = &sourced.origin else {
+            continue;
+        };
+        let edges = match sourced.solvability {
+            ClassicalSolvability::Polynomial => &reversed,
+            ClassicalSolvability::NpHard => inclusions,
+        };
+        for (set, path) in reachable(&GraphSet::Property(property.id.clone()), edges) {
+            let GraphSet::Property(id) = set else {
+                continue;
+            };
+            let Some(target) = properties.get(&id) else {
+                continue;
+            };
+            if !known.insert((problem.id.clone(), id, sourced.solvability.clone())) {
+                continue;
+            }
+            res.push((
+                problem.clone(),
+                target.clone(),
+                SourcedSolvability {
+                    solvability: sourced.solvability.clone(),
+                    origin: SolvabilityOrigin::AlongInclusions {
+                        property: property.clone(),
+                        source: source.clone(),
+                        inclusions: path,
+                    },
+                },
+            ));
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+#[path = "tests/solvability.rs"]
+mod tests;
@@ -0,0 +1,81 @@
+use super::*;
+use crate::data::date::Date;
+use crate::work::inference::{PartialResult, WorkRelation};
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn bounded_class(id: &str, parameter: &PreviewParameter) -> GraphClass {
+    GraphClass {
+        id: GraphClassId::new(id),
+        score: 0,
+        name_core: NameCore::new(id),
+        definition: GraphClassDefinition::Parameter(parameter.clone()),
+        variant: GraphClassVariant::GraphClass,
+        tags: vec![],
+    }
+}
+
+fn arc(a: &PreviewParameter, b: &PreviewParameter, cpx: CpxInfo) -> ParameterArcs {
+    let partial_result = PartialResult {
+        handle: 0,
+        created_by: CreatedBy::Directly(PreviewSource {
+            id: PreviewSourceId::from("src"),
+            sourcekey: SourceKey::Online { url: "url".into() },
+            time: Date::empty(),
+        }),
+        relation: WorkRelation::new(&a.id, &b.id),
+        cpx,
+        bound: None,
+    };
+    vec![(a.clone(), b.clone(), partial_result.to_sourced())]
+}
+
+fn linear() -> CpxInfo {
+    CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(CpxTime::Linear),
+    }
+}
+
+#[test]
+fn bounded_parameter_bounds_its_superset() {
+    let (vc, tw) = (parameter("vc"), parameter("tw"));
+    let classes = vec![bounded_class("bounded_vc", &vc)];
+    let res = bridge_gc_par(vec![], &classes, &arc(&vc, &tw, linear()));
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].1.id, tw.id);
+    assert_eq!(res[1].2, ImplicationRelation::Implies);
+}
+
+#[test]
+fn unbounded_parameter_unbounds_its_subset() {
+    let (vc, tw) = (parameter("vc"), parameter("tw"));
+    let gc = (&bounded_class("bounded_vc", &vc)).preview();
+    let res = bridge_gc_par(
+        vec![(gc, tw.clone(), ImplicationRelation::Excludes)],
+        &[],
+        &arc(&vc, &tw, linear()),
+    );
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].1.id, vc.id);
+    assert_eq!(res[1].2, ImplicationRelation::Excludes);
+}
+
+#[test]
+fn bounded_classes_follow_parameters() {
+    let (vc, tw) = (parameter("vc"), parameter("tw"));
+    let classes = vec![bounded_class("bounded_vc", &vc), bounded_class("bounded_tw", &tw)];
+    let relation = PreviewGraphRelationId::from("induced");
+    let res = bridge_gc_gc(&classes, &arc(&vc, &tw, linear()), &relation);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0.id, PreviewGraphClassId::from("bounded_vc"));
+    assert_eq!(res[0].2.relation, ImplicationRelation::Implies);
+    let res = bridge_gc_gc(&classes, &arc(&tw, &vc, CpxInfo::Exclusion), &relation);
+    assert_eq!(res[0].2.relation, ImplicationRelation::Excludes);
+}
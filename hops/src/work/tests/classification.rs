@@ -0,0 +1,61 @@
+use super::*;
+use crate::data::data::NameCore;
+use crate::work::inference::{PartialResult, WorkRelation};
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn source() -> PartialResult {
+    PartialResult {
+        handle: 0,
+        created_by: CreatedBy::Todo,
+        relation: WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b")),
+        cpx: CpxInfo::Unknown,
+        bound: None,
+    }
+}
+
+fn excluded() -> SourcedCpxInfo {
+    SourcedCpxInfo::Exclusion { source: source() }
+}
+
+fn arc(a: &str, b: &str, cpx: SourcedCpxInfo) -> (PreviewParameter, PreviewParameter, SourcedCpxInfo) {
+    (parameter(a), parameter(b), cpx)
+}
+
+fn classes(arcs: &[(PreviewParameter, PreviewParameter, SourcedCpxInfo)]) -> Vec<(String, String, PairClass)> {
+    classify_pairs(arcs)
+        .into_iter()
+        .map(|(a, b, class)| (a.id.to_string(), b.id.to_string(), class))
+        .collect()
+}
+
+#[test]
+fn bound_and_exclusion_make_strict_pair() {
+    let upper = SourcedCpxInfo::Inclusion {
+        mn: None,
+        mx: Some((CpxTime::Linear, source())),
+    };
+    let arcs = vec![
+        arc("a", "b", upper),
+        arc("b", "a", excluded()),
+        arc("a", "c", excluded()),
+        arc("c", "a", excluded()),
+        arc("b", "c", SourcedCpxInfo::Unknown),
+        arc("c", "b", excluded()),
+    ];
+    assert_eq!(
+        classes(&arcs),
+        vec![
+            ("a".into(), "b".into(), PairClass::StrictlyBounds),
+            ("a".into(), "c".into(), PairClass::Incomparable),
+            ("b".into(), "a".into(), PairClass::StrictlyBoundedBy),
+            ("c".into(), "a".into(), PairClass::Incomparable),
+        ]
+    );
+}
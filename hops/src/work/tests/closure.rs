@@ -0,0 +1,156 @@
+use super::*;
+use crate::data::enums::CreatedBy;
+
+fn id(x: &str) -> PreviewParameterId {
+    PreviewParameterId::from(x)
+}
+
+fn relations(facts: &[(&str, &str, CpxInfo)]) -> HashMap<WorkRelation, PartialResult> {
+    facts
+        .iter()
+        .enumerate()
+        .map(|(handle, (a, b, cpx))| {
+            let relation = WorkRelation::new(&id(a), &id(b));
+            let result = PartialResult {
+                handle,
+                created_by: CreatedBy::Todo,
+                relation: relation.clone(),
+                cpx: cpx.clone(),
+                bound: None,
+            };
+            (relation, result)
+        })
+        .collect()
+}
+
+fn upper(time: CpxTime) -> CpxInfo {
+    CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(time),
+    }
+}
+
+fn interner() -> Interner {
+    Interner::new(&[id("a"), id("b"), id("c")])
+}
+
+#[test]
+fn upper_bounds_compose() {
+    let relations = relations(&[
+        ("a", "b", upper(CpxTime::Linear)),
+        ("b", "c", upper(CpxTime::Polynomial)),
+    ]);
+    assert_eq!(
+        derivations(&interner(), &relations),
+        vec![Derivation::Transitive((0, 1), (1, 2), (0, 2))]
+    );
+}
+
+#[test]
+fn constant_bound_is_derived_once() {
+    let relations = relations(&[
+        ("a", "b", upper(CpxTime::Exists)),
+        ("b", "c", upper(CpxTime::Constant)),
+    ]);
+    assert_eq!(derivations(&interner(), &relations).len(), 1);
+}
+
+#[test]
+fn known_bound_is_not_derived() {
+    let relations = relations(&[
+        ("a", "b", upper(CpxTime::Linear)),
+        ("b", "c", upper(CpxTime::Linear)),
+        ("a", "c", upper(CpxTime::Linear)),
+    ]);
+    assert!(derivations(&interner(), &relations).is_empty());
+}
+
+#[test]
+fn equality_copies_relations() {
+    let relations = relations(&[
+        ("a", "b", CpxInfo::Equal),
+        ("b", "a", CpxInfo::Equal),
+        ("b", "c", CpxInfo::Exclusion),
+    ]);
+    assert_eq!(
+        derivations(&interner(), &relations),
+        vec![Derivation::ThroughEquivalence((1, 2), (1, 0), (0, 2))]
+    );
+}
+
+#[test]
+fn exclusion_moves_against_upper_bounds() {
+    let relations = relations(&[
+        ("a", "b", upper(CpxTime::Linear)),
+        ("a", "c", CpxInfo::Exclusion),
+    ]);
+    assert_eq!(
+        derivations(&interner(), &relations),
+        vec![Derivation::Exclusion((0, 1), (0, 2), (1, 2))]
+    );
+}
+
+fn lower(time: CpxTime) -> CpxInfo {
+    CpxInfo::Inclusion {
+        mn: Some(time),
+        mx: None,
+    }
+}
+
+#[test]
+fn lower_bound_moves_to_linear_upper_bounds() {
+    let relations = relations(&[
+        ("a", "b", lower(CpxTime::Exponential)),
+        ("c", "b", upper(CpxTime::Linear)),
+    ]);
+    assert_eq!(
+        derivations(&interner(), &relations),
+        vec![Derivation::LowerBound((0, 1), (2, 1), (0, 2))]
+    );
+}
+
+#[test]
+fn lower_bound_moves_to_linearly_bounded_parameters() {
+    let relations = relations(&[
+        ("a", "b", lower(CpxTime::Exponential)),
+        ("a", "c", upper(CpxTime::Linear)),
+    ]);
+    assert_eq!(
+        derivations(&interner(), &relations),
+        vec![Derivation::LowerBound((0, 1), (0, 2), (2, 1))]
+    );
+}
+
+#[test]
+fn lower_bound_does_not_move_along_polynomial_bounds() {
+    let relations = relations(&[
+        ("a", "b", lower(CpxTime::Exponential)),
+        ("c", "b", upper(CpxTime::Polynomial)),
+    ]);
+    assert!(derivations(&interner(), &relations).is_empty());
+}
+
+#[test]
+fn constant_bound_of_equal_parameters_is_derived() {
+    // the constant bound contradicts the equality and has to reach `combine_parallel`
+    let relations = relations(&[
+        ("a", "b", upper(CpxTime::Constant)),
+        ("b", "c", upper(CpxTime::Linear)),
+        ("a", "c", CpxInfo::Equal),
+        ("c", "a", CpxInfo::Equal),
+    ]);
+    assert!(derivations(&interner(), &relations).contains(&Derivation::Transitive((0, 1), (1, 2), (0, 2))));
+}
+
+#[test]
+fn unbounded_lower_bound_improves_weaker_one() {
+    let relations = relations(&[
+        ("a", "b", lower(CpxTime::Exists)),
+        ("c", "b", upper(CpxTime::Linear)),
+        ("a", "c", lower(CpxTime::Tower)),
+    ]);
+    assert_eq!(
+        derivations(&interner(), &relations),
+        vec![Derivation::LowerBound((0, 1), (2, 1), (0, 2))]
+    );
+}
@@ -0,0 +1,31 @@
+use super::*;
+use crate::data::id::PreviewParameterId;
+use crate::work::inference::{PartialResultsBuilder, WorkRelation};
+
+fn partial_result(handle: usize, cpx: CpxInfo) -> PartialResult {
+    PartialResult {
+        handle,
+        created_by: CreatedBy::Todo,
+        relation: WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b")),
+        cpx,
+        bound: None,
+    }
+}
+
+#[test]
+fn equal_and_exclusion_do_not_compare() {
+    let equal = partial_result(0, CpxInfo::Equal).to_sourced();
+    let exclusion = partial_result(1, CpxInfo::Exclusion).to_sourced();
+    assert!(matches!(equal.compare_to(&exclusion), Err(CombinationError::ExclusionInclusion(..))));
+    assert!(matches!(exclusion.compare_to(&equal), Err(CombinationError::ExclusionInclusion(..))));
+}
+
+#[test]
+fn inclusion_without_bounds_gives_way() {
+    let empty = partial_result(0, CpxInfo::Inclusion { mn: None, mx: None });
+    let exclusion = partial_result(1, CpxInfo::Exclusion);
+    let mut builder = PartialResultsBuilder::new();
+    let res = empty.combine_parallel(&exclusion, &mut builder).unwrap().unwrap();
+    assert_eq!(res.cpx, CpxInfo::Exclusion);
+    assert!(exclusion.combine_parallel(&empty, &mut builder).unwrap().is_none());
+}
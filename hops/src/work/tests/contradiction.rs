@@ -0,0 +1,34 @@
+use super::*;
+use crate::work::inference::{PartialResult, WorkRelation};
+
+fn partial_result(handle: usize, cpx: CpxInfo) -> Box<PartialResult> {
+    Box::new(PartialResult {
+        handle,
+        created_by: CreatedBy::Todo,
+        relation: WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b")),
+        cpx,
+        bound: None,
+    })
+}
+
+#[test]
+fn one_contradiction_per_pair() {
+    let inclusion = CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(CpxTime::Linear),
+    };
+    let errors = vec![
+        CombinationError::ExclusionInclusion(
+            partial_result(0, inclusion.clone()),
+            partial_result(1, CpxInfo::Exclusion),
+        ),
+        CombinationError::ExclusionInclusion(
+            partial_result(2, inclusion),
+            partial_result(3, CpxInfo::Exclusion),
+        ),
+    ];
+    let contradictions = collect_contradictions(errors);
+    assert_eq!(contradictions.len(), 1);
+    assert_eq!(contradictions[0].first, 0);
+    assert_eq!(contradictions[0].second, 1);
+}
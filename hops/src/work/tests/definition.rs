@@ -0,0 +1,53 @@
+use super::*;
+use crate::data::data::NameCore;
+use crate::input::raw::RawData;
+
+fn raw_parameter(id: &str, definition: RawParameterDefinition) -> RawParameter {
+    RawParameter {
+        id: ParameterId::new(id),
+        score: 0,
+        name_core: NameCore::new(id),
+        definition,
+        tags: vec![],
+    }
+}
+
+fn relations(parameters: Vec<RawParameter>) -> DefinedRelations {
+    let mut raw_data = RawData::new();
+    raw_data.parameters = parameters;
+    let preview_collection = PreviewCollection::new(&raw_data);
+    definition_relations(&raw_data.parameters, &preview_collection)
+}
+
+#[test]
+fn intersection_bounds_its_parts() {
+    let res = relations(vec![
+        raw_parameter("a", RawParameterDefinition::GraphText("".into())),
+        raw_parameter("b", RawParameterDefinition::GraphText("".into())),
+        raw_parameter(
+            "ab",
+            RawParameterDefinition::IntersectionParameters(vec!["a".into(), "b".into()]),
+        ),
+    ]);
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].1, WorkRelation::new(&"ab".into(), &"a".into()));
+    assert_eq!(res[1].1, WorkRelation::new(&"ab".into(), &"b".into()));
+}
+
+#[test]
+fn concretizations_surround_higher_order_parameter() {
+    let res = relations(vec![
+        raw_parameter("fw", RawParameterDefinition::BoundsAll("rfw".into())),
+        raw_parameter(
+            "inf_fw",
+            RawParameterDefinition::FromParametricParameter("rfw".into(), Value::Infinity),
+        ),
+        raw_parameter(
+            "one_fw",
+            RawParameterDefinition::FromParametricParameter("rfw".into(), Value::Value(1)),
+        ),
+    ]);
+    assert_eq!(res.len(), 2);
+    assert!(res.iter().any(|x| x.1 == WorkRelation::new(&"inf_fw".into(), &"fw".into())));
+    assert!(res.iter().any(|x| x.1 == WorkRelation::new(&"fw".into(), &"one_fw".into())));
+}
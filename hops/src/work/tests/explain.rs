@@ -0,0 +1,80 @@
+use super::*;
+use crate::data::data::DataFields;
+use crate::work::inference::WorkRelation;
+
+fn partial_result(handle: usize) -> PartialResult {
+    PartialResult {
+        handle,
+        created_by: CreatedBy::Todo,
+        relation: WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b")),
+        cpx: CpxInfo::Unknown,
+        bound: None,
+    }
+}
+
+#[test]
+fn transitive_inclusion_has_two_premises() {
+    assert_eq!(premise_handles(&CreatedBy::TransitiveInclusion(3, 5)), vec![3, 5]);
+}
+
+#[test]
+fn direct_result_is_a_leaf() {
+    assert!(premise_handles(&CreatedBy::Todo).is_empty());
+}
+
+#[test]
+fn relation_handles_are_deduplicated() {
+    let cpx = SourcedCpxInfo::Inclusion {
+        mn: Some((CpxTime::Linear, partial_result(7))),
+        mx: Some((CpxTime::Linear, partial_result(7))),
+    };
+    assert_eq!(relation_handles(&cpx), vec![7]);
+}
+
+/// Result 3 combines result 2 with itself, result 2 follows from the stated results 0 and 1.
+fn shared_premise() -> Data {
+    let result = |handle: usize, created_by: CreatedBy| PartialResult {
+        created_by,
+        cpx: CpxInfo::Inclusion {
+            mn: None,
+            mx: Some(CpxTime::Linear),
+        },
+        ..partial_result(handle)
+    };
+    Data::new(DataFields {
+        partial_results: vec![
+            result(0, CreatedBy::Todo),
+            result(1, CreatedBy::Todo),
+            result(2, CreatedBy::TransitiveInclusion(0, 1)),
+            result(3, CreatedBy::ParallelComposition(2, 2)),
+        ],
+        ..Default::default()
+    })
+}
+
+#[test]
+fn shared_premise_is_expanded_once() {
+    let data = shared_premise();
+    let proof = proof_tree(&data, 3);
+    assert!(!proof.premises[0].repeated);
+    assert_eq!(proof.premises[0].premises.len(), 2);
+    assert!(proof.premises[1].repeated);
+    assert!(proof.premises[1].premises.is_empty());
+    assert_eq!(proof.leaves().len(), 2);
+    let formatted = proof.format(&data, ProofStyle::Plain);
+    assert!(formatted.contains("(by transitivity of the bounds, derivation #2)"));
+    assert!(formatted.contains("(see derivation #2)"));
+}
+
+#[test]
+fn builder_refers_to_earlier_proofs() {
+    let data = shared_premise();
+    let mut builder = ProofBuilder::default();
+    let first = builder.proof_tree(&data, 2);
+    let second = builder.proof_tree(&data, 3);
+    assert!(!first.repeated);
+    assert!(second.premises.iter().all(|premise| premise.repeated));
+    let formatted = ProofTree::format_all(&[first, second], &data, ProofStyle::Plain);
+    assert!(formatted[0].contains("(by transitivity of the bounds, derivation #2)"));
+    assert!(!formatted[1].contains("derivation #3"));
+}
@@ -0,0 +1,93 @@
+use super::*;
+use crate::data::data::NameCore;
+
+fn operation(id: &str) -> PreviewOperation {
+    PreviewOperation {
+        id: PreviewOperationId::from(id),
+        name: NameCore::new(id),
+    }
+}
+
+fn graph_relation(id: &str, ops: &[&str]) -> GraphRelation {
+    GraphRelation {
+        id: GraphRelationId::new(id),
+        name_core: NameCore::new(id),
+        displayed_definition: GraphRelationDefinition::IsomorphicAfterOperations(
+            ops.iter().map(|x| operation(x)).collect(),
+        ),
+    }
+}
+
+/// subgraph, induced subgraph and minor
+fn order() -> GraphRelationOrder {
+    GraphRelationOrder::new(&[
+        graph_relation("subgraph", &["vtx", "edge"]),
+        graph_relation("induced", &["vtx"]),
+        graph_relation("minor", &["vtx", "edge", "contraction"]),
+    ])
+}
+
+fn class(id: &str) -> PreviewGraphClass {
+    PreviewGraphClass {
+        id: PreviewGraphClassId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn implies(a: &str, b: &str, graph_relation: &str) -> (PreviewGraphClass, PreviewGraphClass, InclusionRelationUnderGraphRelation) {
+    (
+        class(a),
+        class(b),
+        InclusionRelationUnderGraphRelation {
+            relation: ImplicationRelation::Implies,
+            graph_relation: PreviewGraphRelationId::from(graph_relation),
+        },
+    )
+}
+
+#[test]
+fn order_follows_operations() {
+    let order = order();
+    let id = PreviewGraphRelationId::from;
+    assert!(order.implies(&id("induced"), &id("subgraph")));
+    assert!(order.implies(&id("subgraph"), &id("minor")));
+    assert!(order.implies(&id("induced"), &id("minor")));
+    assert!(!order.implies(&id("minor"), &id("subgraph")));
+    assert_eq!(order.pairs().len(), 3);
+}
+
+#[test]
+fn inclusions_compose_under_weakest_common_relation() {
+    let res = close_gc_gc(
+        vec![implies("a", "b", "induced"), implies("b", "c", "subgraph")],
+        &order(),
+    );
+    assert_eq!(res.len(), 3);
+    assert_eq!(res[2].0.id, PreviewGraphClassId::from("a"));
+    assert_eq!(res[2].1.id, PreviewGraphClassId::from("c"));
+    assert_eq!(res[2].2.graph_relation, PreviewGraphRelationId::from("subgraph"));
+}
+
+#[test]
+fn implied_inclusions_are_not_repeated() {
+    let res = close_gc_gc(
+        vec![
+            implies("a", "b", "induced"),
+            implies("b", "c", "induced"),
+            implies("a", "c", "induced"),
+        ],
+        &order(),
+    );
+    assert_eq!(res.len(), 3);
+}
+
+#[test]
+fn closed_under_minors_is_closed_under_subgraphs() {
+    let implied = order().also_closed_under(&PreviewGraphRelationId::from("minor"));
+    assert_eq!(
+        implied,
+        vec![PreviewGraphRelationId::from("induced"), PreviewGraphRelationId::from("subgraph")]
+    );
+    assert!(order().also_closed_under(&PreviewGraphRelationId::from("induced")).is_empty());
+}
@@ -0,0 +1,160 @@
+use super::*;
+use crate::work::inference::WorkRelation;
+use crate::data::data::{DataFields, NameCore, Parameter, ParameterDefinition};
+use crate::data::date::Date;
+use crate::data::preview::{PreviewParameter, PreviewSource};
+
+fn partial_result(handle: usize) -> PartialResult {
+    PartialResult {
+        handle,
+        created_by: CreatedBy::Todo,
+        relation: WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b")),
+        cpx: CpxInfo::Unknown,
+        bound: None,
+    }
+}
+
+#[test]
+fn equality_is_a_linear_upper_bound() {
+    let cpx = SourcedCpxInfo::Equal {
+        source: partial_result(3),
+    };
+    let (time, source) = upper_bound(&cpx).unwrap();
+    assert_eq!(time, CpxTime::Linear);
+    assert_eq!(source.handle, 3);
+}
+
+#[test]
+fn lower_bound_alone_is_not_an_upper_bound() {
+    let cpx = SourcedCpxInfo::Inclusion {
+        mn: Some((CpxTime::Linear, partial_result(3))),
+        mx: None,
+    };
+    assert!(upper_bound(&cpx).is_none());
+    assert!(upper_bound(&SourcedCpxInfo::Exclusion { source: partial_result(4) }).is_none());
+}
+
+struct Item {
+    id: &'static str,
+    score: u32,
+}
+
+impl Score for Item {
+    fn score(&self) -> u32 {
+        self.score
+    }
+    fn set_score(&mut self, new_score: u32) {
+        self.score = new_score;
+    }
+}
+
+impl HasId for Item {
+    fn id(&self) -> String {
+        self.id.into()
+    }
+}
+
+fn edge(a: &str, b: &str) -> (String, String) {
+    (a.into(), b.into())
+}
+
+#[test]
+fn implied_edges_are_hidden() {
+    let (a, b, c) = (Item { id: "a", score: 1 }, Item { id: "b", score: 1 }, Item { id: "c", score: 1 });
+    let (shown, edges) = filter_hidden(&[&a, &b, &c], &[edge("a", "b"), edge("b", "c"), edge("a", "c")]);
+    assert_eq!(shown.len(), 3);
+    assert_eq!(edges.len(), 2);
+    assert!(!edges.contains(&edge("a", "c")));
+}
+
+#[test]
+fn equivalent_items_are_drawn_once() {
+    let (a, b, c) = (Item { id: "a", score: 1 }, Item { id: "b", score: 5 }, Item { id: "c", score: 1 });
+    let (shown, edges) = filter_hidden(
+        &[&a, &b, &c],
+        &[edge("a", "b"), edge("b", "a"), edge("a", "c"), edge("b", "c")],
+    );
+    assert_eq!(shown.iter().map(|x| x.id).collect::<Vec<_>>(), vec!["b", "c"]);
+    assert_eq!(edges, vec![edge("b", "c")]);
+}
+
+fn linear() -> CpxInfo {
+    CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(CpxTime::Linear),
+    }
+}
+
+/// Data of parameters a, b and c where each fact is the best known bound of its pair.
+fn stated(facts: Vec<(&str, &str, CpxInfo)>) -> Data {
+    let previews: Vec<PreviewParameter> = ["a", "b", "c"]
+        .iter()
+        .map(|id| PreviewParameter {
+            id: PreviewParameterId::from(*id),
+            score: 1,
+            name_core: NameCore::new(id),
+        })
+        .collect();
+    let preview = |id: &str| previews.iter().find(|x| x.id.to_string() == id).unwrap().clone();
+    let source = PreviewSource {
+        id: PreviewSourceId::from("s"),
+        sourcekey: SourceKey::Online { url: "s".into() },
+        time: Date::empty(),
+    };
+    let partial_results: Vec<PartialResult> = facts
+        .into_iter()
+        .enumerate()
+        .map(|(handle, (a, b, cpx))| PartialResult {
+            handle,
+            created_by: CreatedBy::Directly(source.clone()),
+            relation: WorkRelation::new(&PreviewParameterId::from(a), &PreviewParameterId::from(b)),
+            cpx,
+            bound: None,
+        })
+        .collect();
+    Data::new(DataFields {
+        arc_parameter_parameter: partial_results
+            .iter()
+            .map(|x| {
+                let (a, b) = (x.relation.subset.to_string(), x.relation.superset.to_string());
+                (preview(&a), preview(&b), x.to_sourced())
+            })
+            .collect(),
+        parameters: previews
+            .iter()
+            .map(|x| Parameter {
+                id: ParameterId::new(&x.id.to_string()),
+                name_core: x.name_core.clone(),
+                score: x.score,
+                tags: vec![],
+                definition: ParameterDefinition::Graph(String::new()),
+                timeline: vec![],
+            })
+            .collect(),
+        partial_results,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn facts_implied_by_each_other_are_not_both_redundant() {
+    // a < b and a < c each follow from the others
+    let data = stated(vec![
+        ("a", "b", linear()),
+        ("a", "c", linear()),
+        ("c", "b", linear()),
+        ("b", "c", linear()),
+    ]);
+    let redundant: Vec<usize> = redundant_facts(&data).iter().map(|x| x.fact).collect();
+    assert_eq!(redundant, vec![0]);
+}
+
+#[test]
+fn implied_upper_bound_keeps_stronger_lower_bound() {
+    let bounds = CpxInfo::Inclusion {
+        mn: Some(CpxTime::Linear),
+        mx: Some(CpxTime::Exponential),
+    };
+    let data = stated(vec![("a", "b", bounds), ("a", "c", linear()), ("c", "b", linear())]);
+    assert!(redundant_facts(&data).is_empty());
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::data::data::NameCore;
+use crate::data::id::SourceId;
+use crate::work::inference::{PartialResult, WorkRelation};
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn source() -> PartialResult {
+    PartialResult {
+        handle: 0,
+        created_by: CreatedBy::Todo,
+        relation: WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b")),
+        cpx: CpxInfo::Unknown,
+        bound: None,
+    }
+}
+
+#[test]
+fn known_bounds_keep_only_resolved_pairs_of_the_figure() {
+    let upper = SourcedCpxInfo::Inclusion {
+        mn: None,
+        mx: Some((CpxTime::Linear, source())),
+    };
+    let arcs = vec![
+        (parameter("a"), parameter("b"), upper.clone()),
+        (parameter("b"), parameter("a"), SourcedCpxInfo::Exclusion { source: source() }),
+        (parameter("a"), parameter("c"), upper),
+        (parameter("c"), parameter("a"), SourcedCpxInfo::Unknown),
+    ];
+    let known: Vec<(String, String, bool)> = known_bounds(&[parameter("a"), parameter("b")], &arcs)
+        .into_iter()
+        .map(|(a, b, bounded)| (a.to_string(), b.to_string(), bounded))
+        .collect();
+    assert_eq!(known, vec![("a".into(), "b".into(), true), ("b".into(), "a".into(), false)]);
+}
+
+fn dated(id: &str, year: i32) -> (PreviewSourceId, Source) {
+    let source = Source {
+        id: SourceId::new(id),
+        score: 0,
+        name_core: NameCore::new(id),
+        sourcekey: SourceKey::Online { url: id.into() },
+        wrote: vec![],
+        time: Date {
+            year: Some(year),
+            month: None,
+            day: None,
+        },
+        drawings: vec![],
+    };
+    (PreviewSourceId::from(id), source)
+}
+
+#[test]
+fn history_ignores_newer_sources() {
+    let sources = HashMap::from([dated("old", 2000), dated("new", 2010)]);
+    let date = Date {
+        year: Some(2005),
+        month: None,
+        day: None,
+    };
+    let key = |fingerprints: &[(PreviewSourceId, Fingerprint)]| {
+        history_fingerprint(1, fingerprints, &sources, &date, &[parameter("a")])
+    };
+    let before = key(&[(PreviewSourceId::from("old"), 1), (PreviewSourceId::from("new"), 2)]);
+    assert_eq!(before, key(&[(PreviewSourceId::from("old"), 1), (PreviewSourceId::from("new"), 3)]));
+    assert_ne!(before, key(&[(PreviewSourceId::from("old"), 4), (PreviewSourceId::from("new"), 2)]));
+}
@@ -0,0 +1,139 @@
+use super::*;
+use crate::data::date::Date;
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn wrote(text: &str) -> Wrote {
+    Wrote {
+        text: text.into(),
+        page: Page::Pp(1),
+        facts: vec![],
+    }
+}
+
+#[test]
+fn changed_source_changes_only_its_fingerprint() {
+    let before = vec![
+        (PreviewSourceId::from("s"), vec![wrote("a")]),
+        (PreviewSourceId::from("t"), vec![wrote("b")]),
+    ];
+    let mut after = before.clone();
+    after[1].1.push(wrote("c"));
+    let (before, after) = (
+        source_fingerprints(&before, &HashMap::new()),
+        source_fingerprints(&after, &HashMap::new()),
+    );
+    assert_eq!(before[0], after[0]);
+    assert_ne!(before[1], after[1]);
+}
+
+#[test]
+fn structure_does_not_depend_on_order() {
+    let structure = |parameters: &[PreviewParameter]| {
+        structure_fingerprint(parameters, &[], &HashMap::new(), &ClassDistances::default(), &vec![])
+    };
+    let (a, b) = (parameter("a"), parameter("b"));
+    assert_eq!(structure(&[a.clone(), b.clone()]), structure(&[b.clone(), a.clone()]));
+    assert_ne!(structure(&[a.clone(), b.clone()]), structure(&[a, b, parameter("c")]));
+}
+
+fn source(id: &str) -> PreviewSource {
+    PreviewSource {
+        id: PreviewSourceId::from(id),
+        sourcekey: SourceKey::Online { url: id.into() },
+        time: Date::empty(),
+    }
+}
+
+fn result(handle: usize, created_by: CreatedBy, a: &str, b: &str) -> PartialResult {
+    PartialResult {
+        handle,
+        created_by,
+        relation: WorkRelation::new(&PreviewParameterId::from(a), &PreviewParameterId::from(b)),
+        cpx: CpxInfo::Inclusion {
+            mn: None,
+            mx: Some(CpxTime::Linear),
+        },
+        bound: None,
+    }
+}
+
+/// Source `s` states a < b, source `t` states b < c, and a < c follows from both.
+/// Source `u` states unrelated d < e and e < f.
+fn processed() -> ProcessedRelations {
+    let closure = ClosureState {
+        partial_results: vec![
+            result(0, CreatedBy::Directly(source("s")), "a", "b"),
+            result(1, CreatedBy::Directly(source("t")), "b", "c"),
+            result(2, CreatedBy::TransitiveInclusion(0, 1), "a", "c"),
+            result(3, CreatedBy::Directly(source("u")), "d", "e"),
+            result(4, CreatedBy::Directly(source("u")), "e", "f"),
+        ],
+        seeds: vec![0, 1, 3, 4],
+        best: vec![0, 1, 2, 3, 4],
+        contradicted: false,
+    };
+    ProcessedRelations {
+        structure: 1,
+        sources: vec![
+            (PreviewSourceId::from("s"), 10),
+            (PreviewSourceId::from("t"), 20),
+            (PreviewSourceId::from("u"), 30),
+        ],
+        closures: vec![(KnowledgeState::Assumed, closure)],
+        history: vec![],
+    }
+}
+
+#[test]
+fn changed_source_drops_dependent_relations() {
+    let current = vec![
+        (PreviewSourceId::from("s"), 10),
+        (PreviewSourceId::from("t"), 21),
+        (PreviewSourceId::from("u"), 30),
+    ];
+    let reused = processed().reuse(1, &current).remove(&KnowledgeState::Assumed).unwrap();
+    assert_eq!(reused.seeds, vec![0, 3, 4]);
+    assert_eq!(reused.relations.len(), 3);
+    assert!(!reused.unchanged.contains(&PreviewSourceId::from("t")));
+    assert!(reused.dropped);
+}
+
+#[test]
+fn removed_source_drops_its_relations() {
+    let current = vec![(PreviewSourceId::from("s"), 10), (PreviewSourceId::from("t"), 20)];
+    let reused = processed().reuse(1, &current).remove(&KnowledgeState::Assumed).unwrap();
+    assert_eq!(reused.seeds, vec![0, 1]);
+    assert_eq!(reused.relations.len(), 3);
+}
+
+#[test]
+fn contradicted_closure_is_not_reused() {
+    let mut processed = processed();
+    processed.closures[0].1.contradicted = true;
+    let current = processed.sources.clone();
+    assert!(processed.reuse(1, &current).is_empty());
+}
+
+#[test]
+fn mostly_changed_sources_are_not_reused() {
+    let current = vec![
+        (PreviewSourceId::from("s"), 11),
+        (PreviewSourceId::from("t"), 20),
+        (PreviewSourceId::from("u"), 31),
+    ];
+    assert!(processed().reuse(1, &current).is_empty());
+}
+
+#[test]
+fn changed_structure_is_not_reused() {
+    let current = processed().sources;
+    assert!(processed().reuse(2, &current).is_empty());
+    assert!(!processed().reuse(1, &current)[&KnowledgeState::Assumed].dropped);
+}
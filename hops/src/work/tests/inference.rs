@@ -0,0 +1,279 @@
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+use super::*;
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn graph_class(id: &str) -> PreviewGraphClass {
+    PreviewGraphClass {
+        id: PreviewGraphClassId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+#[test]
+fn upper_bound_is_single_forward_inclusion() {
+    let (a, b) = (parameter("a"), parameter("b"));
+    let res = cpx_to_work_relations(&a, &b, &Cpx::UpperBound(CpxTime::Linear));
+    assert_eq!(
+        res,
+        vec![(
+            WorkRelation::new(&a.id, &b.id),
+            CpxInfo::Inclusion {
+                mn: None,
+                mx: Some(CpxTime::Linear)
+            }
+        )]
+    );
+}
+
+#[test]
+fn equal_goes_both_ways() {
+    let (a, b) = (parameter("a"), parameter("b"));
+    let res = cpx_to_work_relations(&a, &b, &Cpx::Equal);
+    assert_eq!(res.len(), 2);
+    assert!(res.contains(&(WorkRelation::new(&b.id, &a.id), CpxInfo::Equal)));
+}
+
+#[test]
+fn strict_upper_bound_excludes_backwards() {
+    let (a, b) = (parameter("a"), parameter("b"));
+    let res = cpx_to_work_relations(&a, &b, &Cpx::StrictUpperBound(CpxTime::Polynomial));
+    assert!(res.contains(&(WorkRelation::new(&b.id, &a.id), CpxInfo::Exclusion)));
+}
+
+#[test]
+fn class_inclusion_reverses_onto_distances() {
+    let (cluster, cograph) = (graph_class("cluster"), graph_class("cograph"));
+    let (dc, dg) = (parameter("dist_cluster"), parameter("dist_cograph"));
+    let plain = PreviewGraphRelationId::from("subgraph");
+    let class_distances = ClassDistances {
+        distances: HashMap::from([
+            (cluster.id.clone(), vec![dc.clone()]),
+            (cograph.id.clone(), vec![dg.clone()]),
+        ]),
+        plain: HashSet::from([plain.clone()]),
+    };
+    let inclusion = |relation: ImplicationRelation, graph_relation: &PreviewGraphRelationId| {
+        InclusionRelationUnderGraphRelation {
+            relation,
+            graph_relation: graph_relation.clone(),
+        }
+    };
+    let res = class_distance_relations(
+        &cluster,
+        &cograph,
+        &inclusion(ImplicationRelation::Implies, &plain),
+        &class_distances,
+    );
+    assert_eq!(
+        res,
+        cpx_to_work_relations(&dc, &dg, &Cpx::UpperBound(CpxTime::Linear))
+    );
+    let res = class_distance_relations(
+        &cluster,
+        &cograph,
+        &inclusion(ImplicationRelation::Excludes, &plain),
+        &class_distances,
+    );
+    assert!(res.is_empty());
+}
+
+#[test]
+fn class_inclusion_under_other_relation_says_nothing_about_distances() {
+    let (cluster, cograph) = (graph_class("cluster"), graph_class("cograph"));
+    let class_distances = ClassDistances {
+        distances: HashMap::from([
+            (cluster.id.clone(), vec![parameter("dist_cluster")]),
+            (cograph.id.clone(), vec![parameter("dist_cograph")]),
+        ]),
+        plain: HashSet::from([PreviewGraphRelationId::from("subgraph")]),
+    };
+    let minor = InclusionRelationUnderGraphRelation {
+        relation: ImplicationRelation::Implies,
+        graph_relation: PreviewGraphRelationId::from("minor"),
+    };
+    assert!(class_distance_relations(&cluster, &cograph, &minor, &class_distances).is_empty());
+}
+
+/// Closure computed the way it was before it used bit matrices: every triple of parameters
+/// is checked for a serial composition, a copy through an equivalence, an exclusion or a
+/// lower bound moved along a linear bound, and the results are merged by `combine_parallel`
+/// until nothing improves.
+fn pairwise_closure(
+    ids: &[PreviewParameterId],
+    res: &mut HashMap<WorkRelation, PartialResult>,
+    contradictions: &mut Vec<CombinationError>,
+    builder: &mut PartialResultsBuilder,
+) {
+    loop {
+        let mut updated_relations: VecDeque<WorkRelation> = VecDeque::new();
+        for x in ids {
+            for y in ids {
+                for z in ids {
+                    if x == y || y == z || x == z {
+                        continue;
+                    }
+                    let get = |a, b| res.get(&WorkRelation::new(a, b)).map(|r| r.to_sourced());
+                    let mut derived: Vec<(CreatedBy, CpxInfo, WorkRelation)> = vec![];
+                    // inclusion xy and inclusion yz imply inclusion xz
+                    if let (
+                        Some(SourcedCpxInfo::Inclusion { mx: Some(first), .. }),
+                        Some(SourcedCpxInfo::Inclusion { mx: Some(second), .. }),
+                    ) = (get(x, y), get(y, z))
+                    {
+                        let (a, b, time) = combine_serial(first, second);
+                        derived.push((
+                            CreatedBy::TransitiveInclusion(a.handle, b.handle),
+                            CpxInfo::Inclusion { mn: None, mx: Some(time) },
+                            WorkRelation::new(x, z),
+                        ));
+                    }
+                    // equivalence xy copies the relations between z and x onto z and y
+                    if let Some(SourcedCpxInfo::Equal { source }) = get(x, y) {
+                        for (c, d, e, f) in [(z, x, z, y), (x, z, y, z)] {
+                            if let Some(cd) = res.get(&WorkRelation::new(c, d)) {
+                                derived.push((
+                                    CreatedBy::SameThroughEquivalence(cd.handle, source.handle),
+                                    cd.cpx.clone(),
+                                    WorkRelation::new(e, f),
+                                ));
+                            }
+                        }
+                    }
+                    // inclusion xy and exclusion xz imply exclusion yz,
+                    // inclusion yz and exclusion xz imply exclusion xy
+                    for (inclusion, target) in [((x, y), (y, z)), ((y, z), (x, y))] {
+                        if let (
+                            Some(SourcedCpxInfo::Inclusion { mx: Some((_, smx)), .. }),
+                            Some(SourcedCpxInfo::Exclusion { source }),
+                        ) = (get(inclusion.0, inclusion.1), get(x, z))
+                        {
+                            derived.push((
+                                CreatedBy::TransitiveExclusion(smx.handle, source.handle),
+                                CpxInfo::Exclusion,
+                                WorkRelation::new(target.0, target.1),
+                            ));
+                        }
+                    }
+                    // lower bound xy moves along a linear bound zy onto xz and along a linear bound xz onto zy
+                    if let Some(SourcedCpxInfo::Inclusion { mn: Some((mn, smn)), .. }) = get(x, y) {
+                        for (linear, target) in [((z, y), (x, z)), ((x, z), (z, y))] {
+                            if let Some(SourcedCpxInfo::Inclusion {
+                                mx: Some((CpxTime::Linear, smx)),
+                                ..
+                            }) = get(linear.0, linear.1)
+                            {
+                                derived.push((
+                                    CreatedBy::TransitiveLowerBound(smn.handle, smx.handle),
+                                    CpxInfo::Inclusion { mn: Some(mn.clone()), mx: None },
+                                    WorkRelation::new(target.0, target.1),
+                                ));
+                            }
+                        }
+                    }
+                    for (created_by, cpx, relation) in derived {
+                        let partial_result = builder.partial_result(created_by, cpx, relation);
+                        add_and_update(partial_result, res, &mut updated_relations, contradictions, builder);
+                    }
+                }
+            }
+        }
+        if updated_relations.is_empty() {
+            break;
+        }
+    }
+}
+
+/// Random relations between the parameters drawn from `kinds`, equalities are stated both ways.
+fn random_relations(rng: &mut StdRng, ids: &[PreviewParameterId], kinds: &[CpxInfo]) -> Vec<(WorkRelation, CpxInfo)> {
+    let mut res = vec![];
+    for _ in 0..rng.random_range(3..9) {
+        let (a, b) = (rng.random_range(0..ids.len()), rng.random_range(0..ids.len()));
+        if a == b {
+            continue;
+        }
+        let (a, b) = (&ids[a], &ids[b]);
+        let cpx = kinds[rng.random_range(0..kinds.len())].clone();
+        if cpx == CpxInfo::Equal {
+            res.push((WorkRelation::new(b, a), CpxInfo::Equal));
+        }
+        res.push((WorkRelation::new(a, b), cpx));
+    }
+    res
+}
+
+/// Compares the closures of random relations that do not contradict each other, which of
+/// two contradicting results is kept depends on the order they are found in.
+fn assert_closures_match(kinds: &[CpxInfo]) {
+    let ids: Vec<PreviewParameterId> = ["a", "b", "c", "d", "e"].into_iter().map(PreviewParameterId::from).collect();
+    let interner = Interner::new(&ids);
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut compared = 0;
+    for _ in 0..500 {
+        let relations = random_relations(&mut rng, &ids, kinds);
+        let closed = |closure: &dyn Fn(&mut HashMap<_, _>, &mut Vec<_>, &mut PartialResultsBuilder)| {
+            let (mut res, mut contradictions) = (HashMap::new(), vec![]);
+            let mut builder = PartialResultsBuilder::new();
+            for (relation, cpx) in &relations {
+                let partial_result = builder.partial_result(CreatedBy::Todo, cpx.clone(), relation.clone());
+                add_and_update(partial_result, &mut res, &mut VecDeque::new(), &mut contradictions, &mut builder);
+            }
+            closure(&mut res, &mut contradictions, &mut builder);
+            let cpx: HashMap<WorkRelation, CpxInfo> = res.into_iter().map(|(k, v)| (k, v.cpx)).collect();
+            (cpx, contradictions.is_empty())
+        };
+        let (pairwise, consistent) = closed(&|res, contradictions, builder| {
+            pairwise_closure(&ids, res, contradictions, builder)
+        });
+        let (bit_matrix, bit_matrix_consistent) = closed(&|res, contradictions, builder| {
+            close(&interner, res, &mut VecDeque::new(), contradictions, builder, &mut HashSet::new())
+        });
+        assert_eq!(consistent, bit_matrix_consistent, "{:?}", relations);
+        if consistent {
+            assert_eq!(pairwise, bit_matrix, "{:?}", relations);
+            compared += 1;
+        }
+    }
+    assert!(compared > 250, "only {} closures compared", compared);
+}
+
+const TIMES: [CpxTime; 6] = [
+    CpxTime::Constant,
+    CpxTime::Linear,
+    CpxTime::Polynomial,
+    CpxTime::Exponential,
+    CpxTime::Tower,
+    CpxTime::Exists,
+];
+
+fn upper(time: CpxTime) -> CpxInfo {
+    CpxInfo::Inclusion { mn: None, mx: Some(time) }
+}
+
+#[test]
+fn bit_matrix_closure_matches_pairwise_closure() {
+    let mut kinds = vec![CpxInfo::Equal, CpxInfo::Exclusion];
+    kinds.extend(TIMES.map(upper));
+    assert_closures_match(&kinds);
+}
+
+/// An equality or a constant bound replaces a linear lower bound, after which the lower
+/// bound is no longer moved, so the order of the steps matters once either is stated.
+#[test]
+fn bit_matrix_closure_matches_pairwise_closure_with_lower_bounds() {
+    let mut kinds = vec![CpxInfo::Exclusion];
+    for time in &TIMES[1..] {
+        kinds.push(upper(time.clone()));
+        kinds.push(CpxInfo::Inclusion { mn: Some(time.clone()), mx: None });
+    }
+    assert_closures_match(&kinds);
+}
@@ -0,0 +1,122 @@
+use super::*;
+use crate::data::date::Date;
+use crate::input::raw::{RawData, RawParameter};
+use crate::input::raw_enums::RawParameterDefinition;
+use crate::input::source::Cpx;
+use crate::work::inference::process_relations;
+use crate::work::preview_collection::PreviewCollection;
+
+/// States of the pairs related by linear upper bounds `facts`, each stated by its own source.
+fn knowledge(facts: &[(&str, &str, WroteStatus)]) -> HashMap<(String, String), KnowledgeState> {
+    let mut raw_data = RawData::new();
+    for id in ["a", "b", "c", "d", "e"] {
+        raw_data.parameters.push(RawParameter {
+            id: ParameterId::new(id),
+            score: 0,
+            name_core: NameCore::new(id),
+            definition: RawParameterDefinition::GraphText("".into()),
+            tags: vec![],
+        });
+    }
+    let preview_collection = PreviewCollection::new(&raw_data);
+    let parameter = |id: &str| preview_collection.parameters_previews[&PreviewParameterId::from(id)].clone();
+    let mut sources = HashMap::new();
+    let mut factoids = vec![];
+    for (i, (a, b, status)) in facts.iter().enumerate() {
+        let id = format!("s{i}");
+        let wrote = Wrote {
+            text: "".into(),
+            page: Page::NotApplicable,
+            facts: vec![(
+                PreviewShowedId::from(id.as_str()),
+                status.clone(),
+                Fact::Relation(Relation::ParPar(parameter(a), parameter(b), Cpx::UpperBound(CpxTime::Linear))),
+            )],
+        };
+        sources.insert(
+            PreviewSourceId::from(id.as_str()),
+            Source {
+                id: SourceId::new(&id),
+                score: 0,
+                name_core: NameCore::new(&id),
+                sourcekey: SourceKey::Online { url: id.clone() },
+                wrote: vec![],
+                time: Date::empty(),
+                drawings: vec![],
+            },
+        );
+        factoids.push((PreviewSourceId::from(id.as_str()), vec![wrote]));
+    }
+    let closure = |factoids: &Vec<(PreviewSourceId, Vec<Wrote>)>| {
+        let (arcs, _, _) = process_relations(
+            &[],
+            &HashMap::new(),
+            &ClassDistances::default(),
+            &vec![],
+            factoids,
+            &sources,
+            &preview_collection,
+            None,
+        );
+        arcs
+    };
+    let leveled_arcs: LeveledArcs = LEVELS
+        .iter()
+        .map(|&level| (level, closure(&factoids_at_least(&factoids, level))))
+        .collect();
+    parameter_knowledge(
+        &closure(&factoids),
+        &leveled_arcs,
+        &factoids,
+        &ClassDistances::default(),
+        &preview_collection.parameters_previews,
+    )
+    .into_iter()
+    .filter(|(_, _, state)| *state != KnowledgeState::UnknownToHOPS)
+    .map(|(a, b, state)| ((a.id.to_string(), b.id.to_string()), state))
+    .collect()
+}
+
+fn state(knowledge: &HashMap<(String, String), KnowledgeState>, a: &str, b: &str) -> KnowledgeState {
+    knowledge[&(a.to_string(), b.to_string())]
+}
+
+#[test]
+fn derived_result_is_as_established_as_its_weakest_premise() {
+    let knowledge = knowledge(&[
+        ("a", "b", WroteStatus::Original),
+        ("b", "c", WroteStatus::Assumed),
+        ("a", "b", WroteStatus::TodoStatus),
+    ]);
+    // merely mentioned by the last source but proved by the first
+    assert_eq!(state(&knowledge, "a", "b"), KnowledgeState::Proved);
+    assert_eq!(state(&knowledge, "b", "c"), KnowledgeState::Assumed);
+    assert_eq!(state(&knowledge, "a", "c"), KnowledgeState::Assumed);
+}
+
+#[test]
+fn relation_takes_its_strongest_derivation() {
+    let knowledge = knowledge(&[
+        ("a", "b", WroteStatus::Original),
+        ("b", "c", WroteStatus::Assumed),
+        ("c", "e", WroteStatus::Original),
+        ("a", "d", WroteStatus::Original),
+        ("d", "c", WroteStatus::Noted(NotedSource::Omitted)),
+    ]);
+    assert_eq!(state(&knowledge, "b", "c"), KnowledgeState::Assumed);
+    // the weaker derivation of a < c and the relations relying on it do not matter
+    assert_eq!(state(&knowledge, "a", "c"), KnowledgeState::Folklore);
+    assert_eq!(state(&knowledge, "a", "e"), KnowledgeState::Folklore);
+}
+
+#[test]
+fn status_of_omitted_source_is_folklore() {
+    assert_eq!(
+        KnowledgeState::from_status(&WroteStatus::Noted(NotedSource::Omitted)),
+        KnowledgeState::Folklore
+    );
+    assert_eq!(
+        KnowledgeState::Folklore.weaker(KnowledgeState::Conjectured),
+        KnowledgeState::Conjectured
+    );
+}
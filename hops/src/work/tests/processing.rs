@@ -0,0 +1,80 @@
+use super::*;
+use crate::collection::build_collection;
+use crate::input::source::RawWroteStatus;
+
+/// Serialized items in a fixed order.
+fn sorted<T: Serialize>(items: impl IntoIterator<Item = T>) -> Vec<String> {
+    let mut res: Vec<String> = items
+        .into_iter()
+        .map(|x| serde_json::to_string(&x).unwrap())
+        .collect();
+    res.sort();
+    res
+}
+
+/// Everything that follows from the relations between parameters. Proofs are left out,
+/// the closure continued from a previous run may keep a different derivation of an equally
+/// good relation.
+fn derived(data: &Data) -> Vec<Vec<String>> {
+    vec![
+        sorted(data.arc_parameter_parameter.iter().map(|(a, b, cpx)| (&a.id, &b.id, CpxInfo::from(cpx.clone())))),
+        sorted(data.knowledge_parameter_parameter.iter().map(|(a, b, state)| (&a.id, &b.id, state))),
+        sorted(data.pair_classes.iter().map(|(a, b, class)| (&a.id, &b.id, class))),
+        sorted(data.equivalent_parameters.iter().map(|set| sorted(set.iter().map(|x| &x.id)))),
+        sorted(data.sources.values().map(|source| (&source.id, &source.drawings))),
+        sorted(data.contradictions.iter().map(|x| (&x.subset, &x.superset))),
+    ]
+}
+
+/// Facts of a source whose relations are relied on by many others.
+fn facts_of_relied_on_source(collection: &mut RawData) -> &mut Vec<RawWrote> {
+    let (_, wrotes) = collection
+        .factoids
+        .iter_mut()
+        .find(|(id, _)| id == &PreviewSourceId::from("FLSQsw"))
+        .unwrap();
+    wrotes
+}
+
+/// Processes `after` continuing from the processing of `before` and checks it against
+/// processing `after` from scratch.
+fn assert_continues(before: RawData, after: impl Fn() -> RawData) {
+    let (_, processed) = process_raw_data(before, &None, None);
+    let (incremental, _) = process_raw_data(after(), &None, Some(processed));
+    let (full, _) = process_raw_data(after(), &None, None);
+    assert_eq!(derived(&incremental), derived(&full));
+}
+
+#[test]
+fn incremental_run_gives_the_same_data() {
+    assert_continues(build_collection(), build_collection);
+}
+
+#[test]
+fn incremental_run_after_added_facts_gives_the_same_data() {
+    let mut fewer = build_collection();
+    facts_of_relied_on_source(&mut fewer).truncate(3);
+    assert_continues(fewer, build_collection);
+}
+
+#[test]
+fn incremental_run_after_removed_source_gives_the_same_data() {
+    assert_continues(build_collection(), || {
+        let mut collection = build_collection();
+        collection.factoids.retain(|(id, _)| id != &PreviewSourceId::from("FLSQsw"));
+        collection
+    });
+}
+
+#[test]
+fn incremental_run_after_weakened_status_gives_the_same_data() {
+    assert_continues(build_collection(), || {
+        let mut collection = build_collection();
+        for wrote in facts_of_relied_on_source(&mut collection) {
+            for (_, status, _) in &mut wrote.facts {
+                *status = RawWroteStatus::Assumed;
+            }
+        }
+        collection
+    });
+}
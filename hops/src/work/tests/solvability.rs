@@ -0,0 +1,210 @@
+use super::*;
+use crate::data::date::Date;
+use crate::input::source::InclusionRelationUnderGraphRelation;
+use crate::work::inference::{PartialResult, WorkRelation};
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn source() -> PreviewSource {
+    PreviewSource {
+        id: PreviewSourceId::from("src"),
+        sourcekey: SourceKey::Online { url: "url".into() },
+        time: Date::empty(),
+    }
+}
+
+fn stated(parameter: &PreviewParameter, solvability: ParameterizedSolvability) -> ProblemParameterArcs {
+    let problem = PreviewProblem {
+        id: PreviewProblemId::from("prob"),
+        name: NameCore::new("prob"),
+    };
+    vec![(
+        problem,
+        parameter.clone(),
+        SourcedSolvability {
+            solvability,
+            origin: SolvabilityOrigin::Directly(source()),
+        },
+    )]
+}
+
+/// `a` upper bounds `b`
+fn bound(a: &PreviewParameter, b: &PreviewParameter) -> ParameterArcs {
+    let partial_result = PartialResult {
+        handle: 0,
+        created_by: CreatedBy::Directly(source()),
+        relation: WorkRelation::new(&a.id, &b.id),
+        cpx: CpxInfo::Inclusion {
+            mn: None,
+            mx: Some(CpxTime::Linear),
+        },
+        bound: None,
+    };
+    vec![(a.clone(), b.clone(), partial_result.to_sourced())]
+}
+
+#[test]
+fn tractability_moves_to_bounding_parameter() {
+    let (vc, tw) = (parameter("vc"), parameter("tw"));
+    let res = process_parameterized_solvability(
+        stated(&tw, ParameterizedSolvability::Fpt),
+        &bound(&vc, &tw),
+    );
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].1.id, vc.id);
+    assert!(matches!(
+        res[1].2.origin,
+        SolvabilityOrigin::AlongParameterBound { bound: 0, .. }
+    ));
+}
+
+#[test]
+fn hardness_moves_to_bounded_parameter() {
+    let (vc, tw) = (parameter("vc"), parameter("tw"));
+    let res = process_parameterized_solvability(
+        stated(&vc, ParameterizedSolvability::Whard),
+        &bound(&vc, &tw),
+    );
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].1.id, tw.id);
+}
+
+#[test]
+fn tractability_does_not_move_to_bounded_parameter() {
+    let (vc, tw) = (parameter("vc"), parameter("tw"));
+    let res = process_parameterized_solvability(
+        stated(&vc, ParameterizedSolvability::Fpt),
+        &bound(&vc, &tw),
+    );
+    assert_eq!(res.len(), 1);
+}
+
+fn property(id: &str) -> PreviewGraphClassProperty {
+    PreviewGraphClassProperty {
+        id: PreviewGraphClassPropertyId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn class(id: &str) -> PreviewGraphClass {
+    PreviewGraphClass {
+        id: PreviewGraphClassId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+/// `planar` is a subclass of `sparse` which is equivalent to its class property
+fn classical_setup() -> (Inclusions, HashMap<PreviewGraphClassPropertyId, PreviewGraphClassProperty>) {
+    let (planar, sparse) = (class("planar"), class("sparse"));
+    let relations = vec![
+        Relation::GcGc(
+            planar.clone(),
+            sparse.clone(),
+            InclusionRelationUnderGraphRelation {
+                relation: ImplicationRelation::Implies,
+                graph_relation: PreviewGraphRelationId::from("induced"),
+            },
+        ),
+        Relation::GcProp(planar, property("has_planar"), EquivalenceRelation::Equivalent),
+        Relation::GcProp(sparse, property("has_sparse"), EquivalenceRelation::Equivalent),
+    ];
+    let mut inclusions: Inclusions = HashMap::new();
+    for relation in relations {
+        let (a, b) = match &relation {
+            Relation::GcGc(a, b, _) => (GraphSet::Class(a.id.clone()), GraphSet::Class(b.id.clone())),
+            Relation::GcProp(a, b, _) => (GraphSet::Class(a.id.clone()), GraphSet::Property(b.id.clone())),
+            _ => unreachable!(),
+        };
+        if matches!(relation, Relation::GcProp(..)) {
+            inclusions.entry(b.clone()).or_default().push((a.clone(), relation.clone()));
+        }
+        inclusions.entry(a).or_default().push((b, relation));
+    }
+    let properties = ["has_planar", "has_sparse"]
+        .into_iter()
+        .map(|x| (PreviewGraphClassPropertyId::from(x), property(x)))
+        .collect();
+    (inclusions, properties)
+}
+
+fn stated_classical(property: &PreviewGraphClassProperty, solvability: ClassicalSolvability) -> ProblemPropertyArcs {
+    let problem = PreviewProblem {
+        id: PreviewProblemId::from("prob"),
+        name: NameCore::new("prob"),
+    };
+    vec![(
+        problem,
+        property.clone(),
+        SourcedSolvability {
+            solvability,
+            origin: SolvabilityOrigin::Directly(source()),
+        },
+    )]
+}
+
+#[test]
+fn polynomial_moves_to_subclass() {
+    let (inclusions, properties) = classical_setup();
+    let res = process_classical_solvability(
+        stated_classical(&property("has_sparse"), ClassicalSolvability::Polynomial),
+        &inclusions,
+        &properties,
+    );
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].1.id, PreviewGraphClassPropertyId::from("has_planar"));
+    let SolvabilityOrigin::AlongInclusions { inclusions, .. } = &res[1].2.origin else {
+        panic!("expected a derived result");
+    };
+    assert_eq!(inclusions.len(), 3);
+}
+
+#[test]
+fn np_hardness_moves_to_superclass() {
+    let (inclusions, properties) = classical_setup();
+    let res = process_classical_solvability(
+        stated_classical(&property("has_planar"), ClassicalSolvability::NpHard),
+        &inclusions,
+        &properties,
+    );
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[1].1.id, PreviewGraphClassPropertyId::from("has_sparse"));
+    let poly = process_classical_solvability(
+        stated_classical(&property("has_planar"), ClassicalSolvability::Polynomial),
+        &inclusions,
+        &properties,
+    );
+    assert_eq!(poly.len(), 1);
+}
+
+#[test]
+fn only_plain_established_class_inclusions_are_followed() {
+    let (a, b, c, d) = (class("a"), class("b"), class("c"), class("d"));
+    let under = |relation: &str| InclusionRelationUnderGraphRelation {
+        relation: ImplicationRelation::Implies,
+        graph_relation: PreviewGraphRelationId::from(relation),
+    };
+    let arcs = vec![
+        (a.clone(), b.clone(), under("subgraph")),
+        (b.clone(), c.clone(), under("minor")),
+        (c.clone(), d.clone(), under("subgraph")),
+    ];
+    let knowledge = vec![
+        (a.clone(), b.clone(), KnowledgeState::Mentioned),
+        (b.clone(), c.clone(), KnowledgeState::Proved),
+        (c.clone(), d.clone(), KnowledgeState::Conjectured),
+    ];
+    let mut inclusions: Inclusions = HashMap::new();
+    add_class_inclusions(&mut inclusions, &arcs, &knowledge, |relation| {
+        *relation == PreviewGraphRelationId::from("subgraph")
+    });
+    assert_eq!(inclusions.len(), 1);
+    assert_eq!(inclusions[&GraphSet::Class(a.id.clone())].len(), 1);
+}
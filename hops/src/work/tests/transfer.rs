@@ -0,0 +1,53 @@
+use super::*;
+use crate::data::data::NameCore;
+
+fn parameter(id: &str) -> PreviewParameter {
+    PreviewParameter {
+        id: PreviewParameterId::from(id),
+        score: 0,
+        name_core: NameCore::new(id),
+    }
+}
+
+fn transferred(cpx: CpxInfo) -> Vec<PartialResult> {
+    let transfers = HashMap::from([(
+        TransferGroup::DistanceTo,
+        HashMap::from([
+            (PreviewParameterId::from("a"), vec![parameter("da")]),
+            (PreviewParameterId::from("b"), vec![parameter("db")]),
+        ]),
+    )]);
+    let mut builder = PartialResultsBuilder::new();
+    let relation = WorkRelation::new(&PreviewParameterId::from("a"), &PreviewParameterId::from("b"));
+    let partial_result = builder.partial_result(CreatedBy::Todo, cpx, relation);
+    apply_transfers(&transfers, &partial_result, &mut builder)
+}
+
+#[test]
+fn only_upper_bound_is_transferred() {
+    let res = transferred(CpxInfo::Inclusion {
+        mn: Some(CpxTime::Polynomial),
+        mx: Some(CpxTime::Exponential),
+    });
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        res[0].relation,
+        WorkRelation::new(&PreviewParameterId::from("da"), &PreviewParameterId::from("db"))
+    );
+    assert_eq!(
+        res[0].cpx,
+        CpxInfo::Inclusion {
+            mn: None,
+            mx: Some(CpxTime::Exponential)
+        }
+    );
+}
+
+#[test]
+fn lower_bound_alone_is_not_transferred() {
+    let res = transferred(CpxInfo::Inclusion {
+        mn: Some(CpxTime::Polynomial),
+        mx: None,
+    });
+    assert!(res.is_empty());
+}
@@ -1,62 +1,97 @@
-// // transfers ///////////////////////////////////////////////////////////////
-// let mut transfers: HashMap<TransferGroup, HashMap<PreviewParameterId, Vec<PreviewParameter>>> =
-//     HashMap::new();
-// for (key, raw_pairs) in &raw_transfer {
-//     let mut res: HashMap<PreviewParameterId, Vec<PreviewParameter>> = HashMap::new();
-//     for raw_pair in raw_pairs {
-//         let (from, to) = raw_pair.clone();
-//         let res_from: PreviewParameter = preview_collection
-//             .preview_set_map
-//             .get(&from)
-//             .unwrap()
-//             .clone();
-//         let res_to: PreviewParameter = preview_collection.preview_set_map.get(&to).unwrap().clone();
-//         res.entry(res_from.id).or_default().push(res_to);
-//     }
-//     transfers.insert(key.clone(), res);
-// }
+//! Transfer groups carry relations between parameters over to their counterparts.
 
-fn apply_transfers(
+use std::collections::{HashMap, HashSet};
+
+use crate::data::enums::*;
+use crate::data::id::*;
+use crate::data::preview::*;
+use crate::input::raw::RawParameter;
+use crate::input::raw_enums::RawParameterDefinition;
+use crate::work::inference::{PartialResult, PartialResultsBuilder, WorkRelation};
+use crate::work::preview_collection::PreviewCollection;
+
+/// Base parameter of every `distance_to(parameter)`, bounds between the bases carry over
+/// to the distances.
+pub fn distance_to_transfers<'a>(
+    parameters: impl Iterator<Item = &'a RawParameter>,
+    preview_collection: &PreviewCollection,
+) -> HashMap<TransferGroup, HashMap<PreviewParameterId, Vec<PreviewParameter>>> {
+    let mut res: HashMap<PreviewParameterId, Vec<PreviewParameter>> = HashMap::new();
+    for parameter in parameters {
+        if let RawParameterDefinition::DistanceToParameter(base) = &parameter.definition {
+            let Some(distance) = preview_collection.parameters_previews.get(&parameter.id.preview()) else {
+                continue;
+            };
+            res.entry(base.clone()).or_default().push(distance.clone());
+        }
+    }
+    HashMap::from([(TransferGroup::DistanceTo, res)])
+}
+
+/// Parameters defined as `distance_to(graph_class)` by their graph class. If class A is
+/// included in class B then the distance to A upper bounds the distance to B, as long as
+/// every graph of A is a graph of B.
+#[derive(Debug, Default)]
+pub struct ClassDistances {
+    pub distances: HashMap<PreviewGraphClassId, Vec<PreviewParameter>>,
+    /// Graph relations under which an inclusion of classes is a plain inclusion.
+    pub plain: HashSet<PreviewGraphRelationId>,
+}
+
+pub fn class_distances<'a>(
+    parameters: impl Iterator<Item = &'a RawParameter>,
+    plain: HashSet<PreviewGraphRelationId>,
+    preview_collection: &PreviewCollection,
+) -> ClassDistances {
+    let mut distances: HashMap<PreviewGraphClassId, Vec<PreviewParameter>> = HashMap::new();
+    for parameter in parameters {
+        if let RawParameterDefinition::DistanceToGraphClass(class) = &parameter.definition {
+            let Some(distance) = preview_collection.parameters_previews.get(&parameter.id.preview()) else {
+                continue;
+            };
+            distances.entry(class.clone()).or_default().push(distance.clone());
+        }
+    }
+    ClassDistances { distances, plain }
+}
+
+/// Upper bound of the partial result carried over to the counterparts of its parameters. A lower
+/// bound between two parameters says nothing about their counterparts and is not carried over.
+pub fn apply_transfers(
     transfers: &HashMap<TransferGroup, HashMap<PreviewParameterId, Vec<PreviewParameter>>>,
     partial_result: &PartialResult,
     partial_results_builder: &mut PartialResultsBuilder,
 ) -> Vec<PartialResult> {
     let mut transferred_relations: Vec<PartialResult> = Vec::new();
+    let CpxInfo::Inclusion { mx: Some(mx), .. } = &partial_result.cpx else {
+        return transferred_relations;
+    };
+    let res_cpx = CpxInfo::Inclusion {
+        mn: None,
+        mx: Some(match mx {
+            // todo get rid of these exceptions via lambda that takes the result and transforms it
+            CpxTime::Constant => CpxTime::Linear,
+            x => x.clone(),
+        }),
+    };
     let top = &partial_result.relation.subset;
     let bot = &partial_result.relation.superset;
     for (transfer_group, map) in transfers.iter() {
         if let (Some(top_res), Some(bot_res)) = (map.get(top), map.get(bot)) {
-            let mut res_cpx: SourcedCpxInfo = partial_result.clone().to_sourced();
-            let okay = match res_cpx.clone() {
-                SourcedCpxInfo::Inclusion { mn, mx } => {
-                    res_cpx = SourcedCpxInfo::Inclusion {
-                        mn,
-                        mx: match mx {
-                            // todo get rid of these exceptions via lambda that takes the result and transforms it
-                            Some((Constant, smx)) => Some((CpxTime::Linear, smx)),
-                            x => x,
-                        },
-                    };
-                    true
-                }
-                _ => false,
-            };
-            if okay {
-                let created_by =
-                    CreatedBy::TransferredFrom(transfer_group.clone(), partial_result.handle);
-                for tr in top_res {
-                    for br in bot_res {
-                        let key = WorkRelation::new(&tr.id, &br.id);
-                        let res = partial_results_builder.partial_result(
-                            created_by.clone(),
-                            res_cpx.clone().into(),
-                            key,
-                        );
-                        transferred_relations.push(res);
-                    }
+            let created_by = CreatedBy::TransferredFrom(transfer_group.clone(), partial_result.handle);
+            for tr in top_res {
+                for br in bot_res {
+                    let key = WorkRelation::new(&tr.id, &br.id);
+                    let res =
+                        partial_results_builder.partial_result(created_by.clone(), res_cpx.clone(), key);
+                    transferred_relations.push(res);
                 }
             }
         }
     }
     transferred_relations
 }
+
+#[cfg(test)]
+#[path = "tests/transfer.rs"]
+mod tests;